@@ -22,6 +22,15 @@ const OPTION_FLAGS: [char; 5] = [
     'd', // directory
 ];
 
+/// List of the possible long options, written as "--<name>=<value>" or
+/// "--<name>"
+const LONG_OPTIONS: [&str; 4] = [
+    "only-check",
+    "skip-check",
+    "no-bounds-checks",
+    "emit",
+];
+
 pub struct Args {
     sys_args: Vec<String>,
 
@@ -83,6 +92,9 @@ impl Args {
         for (option_flag, _option_value) in &self.options {
             // Check if the option flag is contained into `OPTIONS` too
             let mut is_valid = false;
+            if option_flag.starts_with("--") {
+                is_valid = LONG_OPTIONS.contains(&&option_flag[2..]);
+            }
             for flag in OPTION_FLAGS {
                 // Index 1 is the letter
                 // "-h", index 0 is "-" and index 1 is "h"
@@ -123,6 +135,17 @@ impl Args {
         let mut key = String::new(); // option flag
 
         for arg in self.sys_args.clone() {
+            // A long option contains its value
+            if arg.starts_with("--") {
+                let (flag, value) = match arg.split_once('=') {
+                    Some((flag, value)) => (flag.to_string(), value.to_string()),
+                    None => (arg.clone(), String::new()),
+                };
+                self.options.insert(flag, value);
+                self.previous_is_option = false;
+                continue;
+            }
+
             // If the option has an empty value, it will be inserted anyway
             if self.is_option(&arg) && self.previous_is_option {
                 key = arg;
@@ -184,4 +207,22 @@ impl Args {
             None => {}
         }
     }
+
+    /// Same as `Self::when_flag()` but for a long option \
+    /// The value is split by commas, to give multiple values to an option
+    pub fn when_long_option<F: FnMut(Vec<String>)>(
+        name: &str, 
+        options: &Dict<String, String>, 
+        mut do_what: F
+    ) {
+        match options.get(format!("--{}", name).as_str()) {
+            Some(value) => do_what(
+                value.split(',')
+                    .filter(| x | !x.is_empty())
+                    .map(| x | x.to_string())
+                    .collect()
+            ),
+            None => {}
+        }
+    }
 }
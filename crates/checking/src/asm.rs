@@ -0,0 +1,428 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+//! Vocabulary of the x86-64 assembly accepted in the inline assembly lines,
+//! following the mnemonics and registers of the "x64asm" crate used by the
+//! compiler to generate the code
+
+use std::fmt;
+
+/// Instruction prefixes, written before the mnemonic
+pub const PREFIXES: [&str; 6] = ["rep", "repe", "repz", "repne", "repnz", "lock"];
+
+/// Keywords that can be used in an operand
+pub const OPERAND_KEYWORDS: [&str; 13] = [
+    "byte", "word", "dword", "qword", "tword", "oword",
+    "rel", "abs", "strict", "near", "far", "short", "wrt",
+];
+
+/// Assembler directives, they have any number of operands
+pub const DIRECTIVES: [&str; 19] = [
+    "db", "dw", "dd", "dq", "dt",
+    "resb", "resw", "resd", "resq",
+    "times", "equ", "align", "section", "global", "extern", "bits",
+    "default", "incbin", "segment",
+];
+
+/// Known mnemonics with their minimum and maximum operands count
+pub const MNEMONICS: &[(&str, usize, usize)] = &[
+    // No operand
+    ("syscall", 0, 0), ("nop", 0, 1), ("leave", 0, 0), ("hlt", 0, 0),
+    ("cld", 0, 0), ("std", 0, 0), ("clc", 0, 0), ("stc", 0, 0), ("cmc", 0, 0),
+    ("cbw", 0, 0), ("cwde", 0, 0), ("cdqe", 0, 0),
+    ("cwd", 0, 0), ("cdq", 0, 0), ("cqo", 0, 0),
+    ("pushf", 0, 0), ("popf", 0, 0), ("pushfq", 0, 0), ("popfq", 0, 0),
+    ("int3", 0, 0), ("ud2", 0, 0), ("cpuid", 0, 0), ("rdtsc", 0, 0),
+    ("pause", 0, 0), ("mfence", 0, 0), ("lfence", 0, 0), ("sfence", 0, 0),
+    ("movsb", 0, 0), ("movsw", 0, 0), ("movsq", 0, 0),
+    ("stosb", 0, 0), ("stosw", 0, 0), ("stosd", 0, 0), ("stosq", 0, 0),
+    ("lodsb", 0, 0), ("lodsw", 0, 0), ("lodsd", 0, 0), ("lodsq", 0, 0),
+    ("scasb", 0, 0), ("scasw", 0, 0), ("scasd", 0, 0), ("scasq", 0, 0),
+    ("cmpsb", 0, 0), ("cmpsw", 0, 0), ("cmpsq", 0, 0),
+    ("ret", 0, 1),
+    // One operand
+    ("push", 1, 1), ("pop", 1, 1), ("inc", 1, 1), ("dec", 1, 1),
+    ("neg", 1, 1), ("not", 1, 1), ("mul", 1, 1), ("div", 1, 1),
+    ("idiv", 1, 1), ("call", 1, 1), ("jmp", 1, 1), ("int", 1, 1),
+    ("bswap", 1, 1), ("loop", 1, 1), ("loope", 1, 1), ("loopne", 1, 1),
+    ("je", 1, 1), ("jne", 1, 1), ("jz", 1, 1), ("jnz", 1, 1),
+    ("jg", 1, 1), ("jge", 1, 1), ("jl", 1, 1), ("jle", 1, 1),
+    ("ja", 1, 1), ("jae", 1, 1), ("jb", 1, 1), ("jbe", 1, 1),
+    ("jc", 1, 1), ("jnc", 1, 1), ("jo", 1, 1), ("jno", 1, 1),
+    ("js", 1, 1), ("jns", 1, 1), ("jp", 1, 1), ("jnp", 1, 1),
+    ("jecxz", 1, 1), ("jrcxz", 1, 1),
+    ("sete", 1, 1), ("setne", 1, 1), ("setz", 1, 1), ("setnz", 1, 1),
+    ("setg", 1, 1), ("setge", 1, 1), ("setl", 1, 1), ("setle", 1, 1),
+    ("seta", 1, 1), ("setae", 1, 1), ("setb", 1, 1), ("setbe", 1, 1),
+    ("setc", 1, 1), ("setnc", 1, 1), ("seto", 1, 1), ("setno", 1, 1),
+    ("sets", 1, 1), ("setns", 1, 1), ("setp", 1, 1), ("setnp", 1, 1),
+    // One to three operands
+    ("imul", 1, 3),
+    // Two operands
+    ("mov", 2, 2), ("movzx", 2, 2), ("movsx", 2, 2), ("movsxd", 2, 2),
+    ("lea", 2, 2), ("xchg", 2, 2), ("add", 2, 2), ("sub", 2, 2),
+    ("adc", 2, 2), ("sbb", 2, 2), ("and", 2, 2), ("or", 2, 2),
+    ("xor", 2, 2), ("cmp", 2, 2), ("test", 2, 2),
+    ("shl", 2, 2), ("shr", 2, 2), ("sal", 2, 2), ("sar", 2, 2),
+    ("rol", 2, 2), ("ror", 2, 2), ("rcl", 2, 2), ("rcr", 2, 2),
+    ("bt", 2, 2), ("bts", 2, 2), ("btr", 2, 2), ("btc", 2, 2),
+    ("bsf", 2, 2), ("bsr", 2, 2), ("popcnt", 2, 2), ("lzcnt", 2, 2),
+    ("tzcnt", 2, 2), ("cmpxchg", 2, 2), ("xadd", 2, 2),
+    ("in", 2, 2), ("out", 2, 2), ("enter", 2, 2),
+    ("cmove", 2, 2), ("cmovne", 2, 2), ("cmovz", 2, 2), ("cmovnz", 2, 2),
+    ("cmovg", 2, 2), ("cmovge", 2, 2), ("cmovl", 2, 2), ("cmovle", 2, 2),
+    ("cmova", 2, 2), ("cmovae", 2, 2), ("cmovb", 2, 2), ("cmovbe", 2, 2),
+    // SSE
+    ("movss", 2, 2), ("movsd", 0, 2), ("movaps", 2, 2), ("movups", 2, 2),
+    ("movapd", 2, 2), ("movupd", 2, 2), ("movd", 2, 2), ("movq", 2, 2),
+    ("addss", 2, 2), ("addsd", 2, 2), ("subss", 2, 2), ("subsd", 2, 2),
+    ("mulss", 2, 2), ("mulsd", 2, 2), ("divss", 2, 2), ("divsd", 2, 2),
+    ("sqrtss", 2, 2), ("sqrtsd", 2, 2),
+    ("ucomiss", 2, 2), ("ucomisd", 2, 2), ("comiss", 2, 2), ("comisd", 2, 2),
+    ("cvtsi2ss", 2, 2), ("cvtsi2sd", 2, 2), ("cvtss2sd", 2, 2),
+    ("cvtsd2ss", 2, 2), ("cvttss2si", 2, 2), ("cvttsd2si", 2, 2),
+    ("xorps", 2, 2), ("xorpd", 2, 2), ("andps", 2, 2), ("andpd", 2, 2),
+    ("pxor", 2, 2),
+    // Three operands
+    ("shld", 3, 3), ("shrd", 3, 3),
+];
+
+/// General purpose registers without a number in their name, the numbered
+/// ones are checked by `is_register()`
+const NAMED_REGISTERS: [&str; 46] = [
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp",
+    "eax", "ebx", "ecx", "edx", "esi", "edi", "ebp", "esp",
+    "ax", "bx", "cx", "dx", "si", "di", "bp", "sp",
+    "al", "bl", "cl", "dl", "sil", "dil", "bpl", "spl",
+    "ah", "bh", "ch", "dh",
+    "cs", "ds", "es", "fs", "gs", "ss",
+    "rip", "eip", "rflags", "eflags",
+];
+
+pub fn is_prefix(word: &str) -> bool {
+    PREFIXES.contains(&word)
+}
+
+pub fn is_directive(word: &str) -> bool {
+    DIRECTIVES.contains(&word)
+}
+
+pub fn is_register(word: &str) -> bool {
+    let word = word.to_lowercase();
+    if NAMED_REGISTERS.contains(&word.as_str()) {
+        return true;
+    }
+
+    // "r8" to "r15" with their "d", "w" and "b" parts
+    if let Some(rest) = word.strip_prefix('r') {
+        let number = rest.trim_end_matches(|c: char| c == 'd' || c == 'w' || c == 'b');
+        if rest.len() - number.len() <= 1 {
+            if let Ok(number) = number.parse::<u8>() {
+                return (8..=15).contains(&number);
+            }
+        }
+        return false;
+    }
+
+    // "xmm0" to "xmm15"
+    if let Some(number) = word.strip_prefix("xmm") {
+        if let Ok(number) = number.parse::<u8>() {
+            return number <= 15;
+        }
+    }
+    false
+}
+
+/// All the registers, to compare an invalid one with them
+fn registers() -> Vec<String> {
+    let mut registers: Vec<String> = NAMED_REGISTERS.iter()
+        .map(| x | x.to_string())
+        .collect();
+
+    for number in 8..=15 {
+        registers.push(format!("r{}", number));
+        for part in ["d", "w", "b"] {
+            registers.push(format!("r{}{}", number, part));
+        }
+    }
+    for number in 0..=15 {
+        registers.push(format!("xmm{}", number));
+    }
+    registers
+}
+
+/// If the word is not a register but is very close to one, like "rbz" or
+/// "r16", so it's probably a mistake
+pub fn looks_like_register(word: &str) -> bool {
+    let word: Vec<char> = word.to_lowercase().chars().collect();
+
+    registers().iter().any(| register | {
+        let register: Vec<char> = register.chars().collect();
+        if register.len() != word.len() {
+            return false;
+        }
+
+        // Only one different character
+        let differences = word.iter()
+            .zip(register.iter())
+            .filter(| (a, b) | a != b)
+            .count();
+        differences == 1 && register[0] == word[0]
+    })
+}
+
+pub fn operands_count(mnemonic: &str) -> Option<(usize, usize)> {
+    MNEMONICS.iter()
+        .find(| (x, _, _) | *x == mnemonic)
+        .map(| (_, min, max) | (*min, *max))
+}
+
+/// An inline assembly line split in its parts
+#[derive(Debug, Clone, Default)]
+pub struct AsmLine {
+    pub label: Option<String>,
+    pub prefix: Option<String>,
+    pub mnemonic: String,
+    pub operands: Vec<String>,
+}
+
+impl AsmLine {
+    /// Parse the code of an inline assembly line, starting by "@" or not
+    pub fn parse(text: &str) -> Self {
+        let mut line = Self::default();
+
+        let mut rest = strip_asm_comment(text.trim().trim_start_matches('@')).trim();
+
+        // Label definition
+        if let Some(first) = rest.split_whitespace().next() {
+            if first.ends_with(':') && !first.contains(|c: char| c == '[' || c == '\'') {
+                line.label = Some(first.trim_end_matches(':').to_string());
+                rest = rest[first.len()..].trim_start();
+            }
+        }
+
+        let mut words = rest.splitn(2, char::is_whitespace);
+        let mut mnemonic = words.next().unwrap_or("").to_lowercase();
+        let mut rest = words.next().unwrap_or("").trim().to_string();
+
+        if is_prefix(&mnemonic) {
+            line.prefix = Some(mnemonic);
+            let mut words = rest.splitn(2, char::is_whitespace);
+            mnemonic = words.next().unwrap_or("").to_lowercase();
+            rest = words.next().unwrap_or("").trim().to_string();
+        }
+
+        line.mnemonic = mnemonic;
+        line.operands = split_operands(&rest);
+        line
+    }
+}
+
+impl fmt::Display for AsmLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut to_write = String::new();
+        if let Some(label) = &self.label {
+            to_write += &format!("{}: ", label);
+        }
+        if let Some(prefix) = &self.prefix {
+            to_write += &format!("{} ", prefix);
+        }
+        to_write += &self.mnemonic;
+        if !self.operands.is_empty() {
+            to_write += &format!(" {}", self.operands.join(", "));
+        }
+        write!(f, "{}", to_write.trim())
+    }
+}
+
+/// Remove the assembly comment (";") at the end of a line
+fn strip_asm_comment(text: &str) -> &str {
+    let mut in_string: Option<char> = None;
+    for (i, c) in text.char_indices() {
+        match in_string {
+            Some(delimiter) => if c == delimiter { in_string = None },
+            None => match c {
+                '"' | '\'' | '`' => in_string = Some(c),
+                ';' => return &text[..i],
+                _ => {}
+            },
+        }
+    }
+    text
+}
+
+/// Split the operands by the commas, except for the ones in a memory
+/// reference or in a string
+fn split_operands(text: &str) -> Vec<String> {
+    let mut operands = vec![];
+    let mut current = String::new();
+    let mut in_string: Option<char> = None;
+    let mut depth = 0;
+
+    for c in text.chars() {
+        match in_string {
+            Some(delimiter) => if c == delimiter { in_string = None },
+            None => match c {
+                '"' | '\'' | '`' => in_string = Some(c),
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    operands.push(current.trim().to_string());
+                    current = String::new();
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+    }
+
+    if !current.trim().is_empty() || !operands.is_empty() {
+        operands.push(current.trim().to_string());
+    }
+    operands
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || "_.?$@".contains(c)
+}
+
+fn is_identifier_continue(c: char) -> bool {
+    c.is_alphanumeric() || "_.?$@#~".contains(c)
+}
+
+/// Rewrite the words of an operand that are registers or symbols with
+/// `rename`, a word is kept when it gives `None` \
+/// The numbers, the strings and the operand keywords are not given
+pub fn map_identifiers<F>(operand: &str, mut rename: F) -> String
+where F: FnMut(&str) -> Option<String>
+{
+    let mut result = String::new();
+    let chars: Vec<char> = operand.chars().collect();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '"' || c == '\'' || c == '`' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += 1;
+            }
+            i = usize::min(i + 1, chars.len());
+            result.extend(chars[start..i].iter());
+            continue;
+        }
+
+        if is_identifier_start(c) || c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && is_identifier_continue(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+
+            let is_keyword = OPERAND_KEYWORDS.contains(&word.to_lowercase().as_str());
+            if c.is_ascii_digit() || is_keyword || word == "$" || word == "$$" {
+                result += &word;
+                continue;
+            }
+
+            match rename(&word) {
+                Some(renamed) => result += &renamed,
+                None => result += &word,
+            }
+            continue;
+        }
+
+        result.push(c);
+        i += 1;
+    }
+    result
+}
+
+/// All the words of an operand that are registers or symbols
+pub fn identifiers(operand: &str) -> Vec<String> {
+    let mut identifiers = vec![];
+    map_identifiers(operand, | x | {
+        identifiers.push(x.to_string());
+        None
+    });
+    identifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use x64asm::{
+        ddirective, ddirective::DefineDirective::*,
+        formatter::Formatter,
+        instruction as i, label,
+        mnemonic::Mnemonic::*,
+        operand::Op,
+        reg, register::Register::*,
+    };
+
+    use super::*;
+
+    /// The names written by "x64asm" for the mnemonics, registers and define
+    /// directives used by the compiler are in the tables
+    #[test]
+    fn tables_follow_x64asm() {
+        let mut formatter = Formatter::new(false);
+        formatter.add_instructions(&mut vec![
+            i!(Global, Op::Label("main".to_string())),
+            i!(label!("main")),
+            i!(Push, reg!(Rbp)),
+            i!(Mov, reg!(Rbp), reg!(Rsp)),
+            i!(Mov, reg!(Rax), Op::Literal(1)),
+            i!(Mov, reg!(Rdi), reg!(Rsi)),
+            i!(Xor, reg!(Rdx), reg!(Rcx)),
+            i!(Xor, reg!(Al), reg!(Al)),
+            i!(Syscall),
+            i!(Pop, reg!(Rbp)),
+            i!(Ret),
+            i!(label!("main.b"), ddirective!(Db), Op::Literal(1)),
+            i!(label!("main.w"), ddirective!(Dw), Op::Literal(1)),
+            i!(label!("main.d"), ddirective!(Dd), Op::Literal(1)),
+            i!(label!("main.q"), ddirective!(Dq), Op::Literal(1)),
+        ]);
+
+        let file = env::temp_dir().join("juc_asm_tables.asm");
+        let path = file.as_path();
+        formatter.to_file(&path);
+        let code = fs::read_to_string(path).unwrap();
+
+        for text in code.lines() {
+            // A label is written with or without ':'
+            let text = text.trim();
+            let text = match text.split_once(char::is_whitespace) {
+                Some((first, rest)) if first.starts_with("main") => rest,
+                _ if text.starts_with("main") => continue,
+                _ => text,
+            };
+
+            let line = AsmLine::parse(text);
+            if line.mnemonic.is_empty() {
+                continue;
+            }
+            assert!(
+                is_directive(&line.mnemonic) || operands_count(&line.mnemonic).is_some(),
+                "unknown mnemonic '{}'",
+                line.mnemonic
+            );
+            for operand in line.operands.iter() {
+                for identifier in identifiers(operand) {
+                    assert!(
+                        identifier == "main" || is_register(&identifier),
+                        "unknown register '{}'",
+                        identifier
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,270 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use jup::tokens::Token;
+
+use logging::*;
+use logging::level::LogLevel;
+use logging::log::Log;
+use logging::logger::Logger;
+
+use crate::asm::{self, AsmLine};
+use crate::base::Checker;
+use crate::data::CheckerData;
+use crate::symbols::Resolution;
+
+/// Check the inline assembly lines before giving them to the assembler, to
+/// report the errors with the right location in the source file
+pub struct AssemblyChecker {
+    data: CheckerData,
+    /// Labels and external symbols defined by the inline assembly lines of
+    /// the source file
+    asm_symbols: Vec<String>,
+}
+
+impl AssemblyChecker {
+    pub fn new(data: CheckerData) -> Self {
+        Self {
+            data,
+            asm_symbols: vec![],
+        }
+    }
+
+    /// Position of the token starting the given word in the line, as
+    /// expected by `line_to_string()`, or 0 when it's not found
+    fn token_position(line: &Vec<Token>, word: &str) -> usize {
+        match line.iter().position(| token | {
+            let token = token.to_string();
+            !token.is_empty() && word.starts_with(&token)
+        }) {
+            Some(token_i) => token_i + 1,
+            None => 0,
+        }
+    }
+
+    fn add_error(&mut self, line: &Vec<Token>, word: &str, title: &str, message: String) {
+        let token_i = Self::token_position(line, word);
+        let cause = source_to_string(
+            self.data().source.clone(),
+            self.data().line_i,
+            if token_i > 0 { token_i - 1 } else { 0 },
+        );
+
+        self.data().logger.add_log(
+            Log::new(
+                LogLevel::Error,
+                title.to_string(),
+                format!("{}{}", line_to_string(line, token_i), message)
+            )
+            .add_cause(cause)
+            .finish()
+        );
+    }
+
+    fn check_operand(&mut self, line: &Vec<Token>, asm_line: &AsmLine, operand: &str) {
+        // The operands of these directives are declarations, not references
+        match asm_line.mnemonic.as_str() {
+            "global" | "extern" | "section" | "segment" | "bits" | "default" => return,
+            _ => {}
+        }
+
+        for identifier in asm::identifiers(operand) {
+            // Local labels are attached to the previous label, they cannot be
+            // resolved here
+            if asm::is_register(&identifier) || identifier.starts_with('.') {
+                continue;
+            }
+            if self.asm_symbols.iter().any(| x | *x == identifier) {
+                continue;
+            }
+
+            let module = self.module();
+            match self.data().project.symbols.resolve(&identifier, &module) {
+                Resolution::Found(_) => {},
+                Resolution::Ambiguous(candidates) => {
+                    let candidates: Vec<String> = candidates.iter()
+                        .map(| x | format!(
+                            "'{}' {}", 
+                            x.id, 
+                            source_to_string(x.source.clone(), x.line_i, 1)
+                        ))
+                        .collect();
+
+                    self.add_error(
+                        line,
+                        &identifier,
+                        "Ambiguous symbol",
+                        format!(
+                            "'{}' can be one of these symbols:\n{}",
+                            identifier,
+                            candidates.join("\n")
+                        ),
+                    );
+                }
+                Resolution::Unresolved if asm::looks_like_register(&identifier) => {
+                    self.add_error(
+                        line,
+                        &identifier,
+                        "Invalid register",
+                        format!("'{}' is not a register", identifier),
+                    );
+                }
+                Resolution::Unresolved => {
+                    self.add_error(
+                        line,
+                        &identifier,
+                        "Unknown symbol",
+                        format!(
+                            "'{}' is not a function or a static variable of the \
+                            project, neither a label of the inline assembly",
+                            identifier
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Module of the checked source file
+    fn module(&mut self) -> String {
+        let source = self.data().source.clone();
+        match self.data().project.sources.iter().find(| x | x.path == source) {
+            Some(source) => source.module(),
+            None => String::new(),
+        }
+    }
+}
+
+/// SEE Functions' documentation from `Checker` because they are not
+/// written here a new time
+impl Checker for AssemblyChecker {
+    fn name(&self) -> &'static str {
+        "assembly"
+    }
+
+    fn needs_valid_syntax(&self) -> bool {
+        false
+    }
+
+    /// Collect the labels and the external symbols of the inline assembly,
+    /// they can be used before their definition
+    fn init(&mut self) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+
+        for (line_i, line) in parsed.iter().enumerate() {
+            match line.first() {
+                Some(Token::Assembly) => {},
+                _ => continue,
+            }
+
+            let asm_line = AsmLine::parse(&lines[line_i].text);
+            if let Some(label) = asm_line.label.clone() {
+                self.asm_symbols.push(label);
+            }
+            if asm_line.mnemonic == "extern" {
+                for operand in asm_line.operands.iter() {
+                    self.asm_symbols.extend(asm::identifiers(operand));
+                }
+            }
+        }
+    }
+
+    fn check(&mut self) {
+        let parsed = self.data().parsed.clone();
+
+        for line in parsed.iter() {
+            let mut previous_token = Token::None;
+            let mut break_line = false;
+
+            self.data().token_i = 0;
+            if let Some(token) = line.first() {
+                self.check_for_instruction(
+                    line,
+                    &mut break_line,
+                    token,
+                    &mut previous_token,
+                );
+            }
+            self.data().line_i += 1;
+        }
+    }
+
+    fn check_for_instruction(
+        &mut self,
+        line: &Vec<Token>,
+        break_line: &mut bool,
+        token: &Token,
+        _previous_token: &mut Token
+    ) {
+        *break_line = true;
+
+        match token {
+            Token::Assembly => {},
+            _ => return,
+        }
+
+        let line_i = self.data().line_i;
+        let asm_line = AsmLine::parse(&self.data().lines[line_i].text.clone());
+
+        if asm_line.mnemonic.is_empty() {
+            if asm_line.label.is_none() {
+                self.add_error(
+                    line,
+                    "",
+                    "Empty inline assembly",
+                    "No instruction was found after the inline assembly token".to_string(),
+                );
+            }
+            return;
+        }
+
+        if !asm::is_directive(&asm_line.mnemonic) {
+            let (min, max) = match asm::operands_count(&asm_line.mnemonic) {
+                Some(count) => count,
+                None => {
+                    self.add_error(
+                        line,
+                        &asm_line.mnemonic.clone(),
+                        "Unknown instruction",
+                        format!("'{}' is not a known x86-64 instruction", asm_line.mnemonic),
+                    );
+                    return;
+                }
+            };
+
+            let count = asm_line.operands.len();
+            if count < min || count > max {
+                let expected = match min == max {
+                    true => format!("{}", min),
+                    false => format!("{} to {}", min, max),
+                };
+                self.add_error(
+                    line,
+                    &asm_line.mnemonic.clone(),
+                    "Invalid operands count",
+                    format!(
+                        "'{}' takes {} operand(s) but {} were given",
+                        asm_line.mnemonic,
+                        expected,
+                        count
+                    ),
+                );
+                return;
+            }
+        }
+
+        for operand in asm_line.operands.iter() {
+            self.check_operand(line, &asm_line, operand);
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), Logger> {
+        self.data().logger.get_result()
+    }
+
+    fn data(&mut self) -> &mut CheckerData {
+        &mut self.data
+    }
+}
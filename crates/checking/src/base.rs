@@ -9,6 +9,26 @@ use logging::logger::Logger;
 use crate::data::CheckerData;
 
 pub trait Checker {
+    /// Name used to select the checker from the command line
+    fn name(&self) -> &'static str;
+
+    /// Names of the checkers that have to be run before this one, it's not
+    /// run when one of them failed
+    fn depends_on(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// If the checker can only work on a syntactically valid source file
+    fn needs_valid_syntax(&self) -> bool {
+        true
+    }
+
+    /// If the checker is run even when it's not selected, because the code
+    /// generation relies on it
+    fn is_required(&self) -> bool {
+        false
+    }
+
     /// Entry point with useful stuff did here
     fn init(&mut self);
 
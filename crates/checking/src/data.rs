@@ -2,15 +2,61 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use std::rc::Rc;
+
 use jup::tokens::Token;
 
 use logging::logger::Logger;
 
+use crate::source::Line;
+use crate::symbols::SymbolTable;
+
+/// A parsed source file of the project
+#[derive(Clone)]
+pub struct SourceFile {
+    pub path: String,
+    pub parsed: Vec<Vec<Token>>,
+    pub lines: Vec<Line>,
+}
+
+impl SourceFile {
+    /// Module name it's the filename without the ".ju" extension \
+    /// SEE `compilation::base::Compiler::run()`
+    pub fn module(&self) -> String {
+        self.path.split(".ju").collect::<String>()
+    }
+}
+
+/// Information about all the source files compiled together, for the
+/// checkers that cannot work on one source file only
+pub struct ProjectData {
+    pub is_library: bool,
+    pub sources: Vec<SourceFile>,
+    pub symbols: SymbolTable,
+}
+
 /// Information for all checker structures
+#[derive(Clone)]
 pub struct CheckerData {
     pub source: String,
     pub parsed: Vec<Vec<Token>>,
+    pub lines: Vec<Line>,
+    pub project: Rc<ProjectData>,
     pub logger: Logger,
     pub line_i: usize,
     pub token_i: usize,
 }
+
+impl CheckerData {
+    pub fn new(source: &SourceFile, project: Rc<ProjectData>) -> Self {
+        Self {
+            source: source.path.clone(),
+            parsed: source.parsed.clone(),
+            lines: source.lines.clone(),
+            project,
+            logger: Logger::new(),
+            line_i: 0,
+            token_i: 0,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use jup::tokens::Token;
+
+use logging::*;
+use logging::level::LogLevel;
+use logging::log::Log;
+use logging::logger::Logger;
+
+use crate::base::Checker;
+use crate::data::CheckerData;
+use crate::entry::ENTRY_POINT;
+
+/// Check that the functions, the static variables, the structures and the
+/// enums defined by a source file are not already defined with the same scoped name in the
+/// project
+pub struct DefinitionsChecker {
+    data: CheckerData,
+}
+
+impl DefinitionsChecker {
+    pub fn new(data: CheckerData) -> Self {
+        Self {
+            data,
+        }
+    }
+}
+
+/// SEE Functions' documentation from `Checker` because they are not
+/// written here a new time
+impl Checker for DefinitionsChecker {
+    fn name(&self) -> &'static str {
+        "definitions"
+    }
+
+    fn needs_valid_syntax(&self) -> bool {
+        false
+    }
+
+    fn init(&mut self) {
+
+    }
+
+    fn check(&mut self) {
+        let parsed = self.data().parsed.clone();
+
+        for line in parsed.iter() {
+            let mut previous_token = Token::None;
+            let mut break_line = false;
+
+            self.data().token_i = 0;
+            if let Some(token) = line.first() {
+                self.check_for_instruction(
+                    line,
+                    &mut break_line,
+                    token,
+                    &mut previous_token,
+                );
+            }
+            self.data().line_i += 1;
+        }
+    }
+
+    fn check_for_instruction(
+        &mut self,
+        line: &Vec<Token>,
+        break_line: &mut bool,
+        _token: &Token,
+        _previous_token: &mut Token
+    ) {
+        *break_line = true;
+
+        let source = self.data().source.clone();
+        let line_i = self.data().line_i;
+
+        // The symbol defined by this line
+        let symbol = match self.data().project.symbols.symbols()
+            .iter()
+            .find(| x | x.source == source && x.line_i == line_i) 
+        {
+            Some(symbol) => symbol.clone(),
+            None => return,
+        };
+
+        // Multiple entry points are reported by `EntryChecker`
+        if symbol.id == ENTRY_POINT && !self.data().project.is_library {
+            return;
+        }
+
+        // Only the first definition of the project is valid
+        let first = self.data().project.symbols.get(&symbol.id)[0].clone();
+        if first.source == source && first.line_i == line_i {
+            return;
+        }
+
+        self.data().logger.add_log(
+            Log::new(
+                LogLevel::Error,
+                "Multiple definitions".to_string(),
+                format!(
+                    "{}'{}' is already defined {}",
+                    line_to_string(line, 2),
+                    symbol.id,
+                    source_to_string(first.source, first.line_i, 1)
+                )
+            )
+            .add_cause(source_to_string(source, line_i, 1))
+            .finish()
+        );
+    }
+
+    fn finish(&mut self) -> Result<(), Logger> {
+        self.data().logger.get_result()
+    }
+
+    fn data(&mut self) -> &mut CheckerData {
+        &mut self.data
+    }
+}
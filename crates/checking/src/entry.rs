@@ -0,0 +1,191 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use jup::tokens::Token;
+
+use logging::*;
+use logging::level::LogLevel;
+use logging::log::Log;
+use logging::logger::Logger;
+
+use objects::type_::Type;
+
+use crate::base::Checker;
+use crate::data::CheckerData;
+use crate::source::{self, Line};
+
+/// Function called by the start file of a binary, re-exported by
+/// `compilation::defaults`
+pub const ENTRY_POINT: &str = "main";
+
+/// Type returned by the function named `id` declared with `return_type` \
+/// The value returned by the entry point is the exit code, it's an "int" when
+/// no type is written
+pub fn return_type(id: &str, return_type: Option<Type>) -> Option<Type> {
+    match return_type {
+        None if id == ENTRY_POINT => Some(Type::I32),
+        return_type => return_type,
+    }
+}
+
+/// Check the entry point according to the build mode: a binary needs exactly
+/// one entry point in all its source files and a library should not have one
+pub struct EntryChecker {
+    data: CheckerData,
+}
+
+impl EntryChecker {
+    pub fn new(data: CheckerData) -> Self {
+        Self {
+            data,
+        }
+    }
+
+    /// If the line is the definition of the entry point
+    pub fn is_entry_point(line: &Vec<Token>, text: &Line) -> bool {
+        source::function_id(line, text).as_deref() == Some(ENTRY_POINT)
+    }
+
+    /// Where the entry point is defined in all the project, as
+    /// `(source, line_i)`
+    fn definitions(&mut self) -> Vec<(String, usize)> {
+        let mut definitions = vec![];
+        for source in self.data().project.sources.iter() {
+            for (line_i, line) in source.parsed.iter().enumerate() {
+                if Self::is_entry_point(line, &source.lines[line_i]) {
+                    definitions.push((source.path.clone(), line_i));
+                }
+            }
+        }
+        definitions
+    }
+}
+
+/// SEE Functions' documentation from `Checker` because they are not
+/// written here a new time
+impl Checker for EntryChecker {
+    fn name(&self) -> &'static str {
+        "entry"
+    }
+
+    fn needs_valid_syntax(&self) -> bool {
+        false
+    }
+
+    fn init(&mut self) {
+
+    }
+
+    fn check(&mut self) {
+        let definitions = self.definitions();
+        let source = self.data().source.clone();
+
+        // Reported once, when checking the first source file
+        let is_first_source = match self.data().project.sources.first() {
+            Some(first) => first.path == source,
+            None => false,
+        };
+
+        if !self.data().project.is_library && definitions.is_empty() && is_first_source {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "No entry point".to_string(),
+                    format!(
+                        "A binary needs a function '{}' but no given source \
+                        file defines it",
+                        ENTRY_POINT
+                    )
+                )
+                .add_hint(format!(
+                    "Define '{} {}' or make a library with the option flag '-l'",
+                    Token::Function.to_string(),
+                    ENTRY_POINT
+                ))
+            );
+        }
+
+        let parsed = self.data().parsed.clone();
+        for line in parsed.iter() {
+            let mut previous_token = Token::None;
+            let mut break_line = false;
+
+            self.data().token_i = 0;
+            if let Some(token) = line.first() {
+                self.check_for_instruction(
+                    line,
+                    &mut break_line,
+                    token,
+                    &mut previous_token,
+                );
+            }
+            self.data().line_i += 1;
+        }
+    }
+
+    fn check_for_instruction(
+        &mut self,
+        line: &Vec<Token>,
+        break_line: &mut bool,
+        _token: &Token,
+        _previous_token: &mut Token
+    ) {
+        *break_line = true;
+
+        let line_i = self.data().line_i;
+        if !Self::is_entry_point(line, &self.data().lines[line_i]) {
+            return;
+        }
+
+        let source = self.data().source.clone();
+        let cause = source_to_string(source.clone(), line_i, 1);
+
+        if self.data().project.is_library {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Warning,
+                    "Entry point in a library".to_string(),
+                    format!(
+                        "{}A library does not need a function '{}', it will \
+                        be compiled as any other function",
+                        line_to_string(line, 2),
+                        ENTRY_POINT
+                    )
+                )
+                .add_cause(cause)
+                .finish()
+            );
+            return;
+        }
+
+        // Only the first definition of the project is valid
+        let (first_source, first_line_i) = self.definitions()[0].clone();
+        if first_source == source && first_line_i == line_i {
+            return;
+        }
+
+        self.data().logger.add_log(
+            Log::new(
+                LogLevel::Error,
+                "Multiple entry points".to_string(),
+                format!(
+                    "{}The function '{}' is already defined {}",
+                    line_to_string(line, 2),
+                    ENTRY_POINT,
+                    source_to_string(first_source, first_line_i, 1)
+                )
+            )
+            .add_cause(cause)
+            .add_hint("A binary can only have one entry point".to_string())
+        );
+    }
+
+    fn finish(&mut self) -> Result<(), Logger> {
+        self.data().logger.get_result()
+    }
+
+    fn data(&mut self) -> &mut CheckerData {
+        &mut self.data
+    }
+}
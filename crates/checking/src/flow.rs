@@ -0,0 +1,228 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use jup::tokens::Token;
+
+use logging::*;
+use logging::level::LogLevel;
+use logging::log::Log;
+use logging::logger::Logger;
+
+use crate::base::Checker;
+use crate::data::CheckerData;
+use crate::source::{self, Line, LoopHeader};
+
+/// Check the control flow of each function of a given source file content:
+/// the function has to end by a return instruction and no code can be placed
+/// after an instruction leaving the function
+pub struct FlowChecker {
+    data: CheckerData,
+}
+
+impl FlowChecker {
+    pub fn new(data: CheckerData) -> Self {
+        Self {
+            data,
+        }
+    }
+
+    fn check_function(&mut self, header_i: usize) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+
+        self.check_unreachable(header_i);
+
+        if block_leaves(&parsed, &lines, header_i) {
+            return;
+        }
+
+        let statements = source::block_statements(&parsed, &lines, header_i);
+        let last_i = match statements.last() {
+            Some(last_i) => *last_i,
+            None => header_i,
+        };
+
+        // An implicit return gives "0", it's not acceptable for a function
+        // that returns values somewhere else
+        let end = source::block_end(&parsed, &lines, header_i);
+        let returns_value = parsed[header_i..end]
+            .iter()
+            .any(|line| matches!(line.first(), Some(Token::Return)) && line.len() > 1);
+
+        let cause = source_to_string(self.data().source.clone(), last_i, 0);
+        let function_id = source::function_id(&parsed[header_i], &lines[header_i])
+            .unwrap_or_default();
+
+        if returns_value {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Missing return".to_string(),
+                    format!(
+                        "{}The function '{}' returns a value but its end is \
+                        reached without a return instruction",
+                        line_to_string(&parsed[last_i], 0),
+                        function_id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(format!(
+                    "Add '{} <value>' at the end of the function",
+                    Token::Return.to_string()
+                ))
+            );
+        } else {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Warning,
+                    "Implicit return".to_string(),
+                    format!(
+                        "{}The function '{}' does not end by a return \
+                        instruction, one is inserted",
+                        line_to_string(&parsed[last_i], 0),
+                        function_id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(format!(
+                    "Add '{}' at the end of the function",
+                    Token::Return.to_string()
+                ))
+            );
+        }
+    }
+
+    /// Warn about the first statement placed after a return, an exit or a
+    /// jump instruction in the block opened by the line `header_i` and its
+    /// nested blocks
+    fn check_unreachable(&mut self, header_i: usize) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+
+        let mut leaving: Option<String> = None;
+        for line_i in source::block_statements(&parsed, &lines, header_i) {
+            if let Some(ref leaving) = leaving {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Warning,
+                        "Unreachable code".to_string(),
+                        format!(
+                            "{}This line is placed after '{}' and will never \
+                            be executed",
+                            line_to_string(&parsed[line_i], 0),
+                            leaving
+                        )
+                    )
+                    .add_cause(cause)
+                    .finish()
+                );
+                return;
+            }
+
+            match parsed[line_i].first() {
+                Some(Token::Return) => leaving = Some(Token::Return.to_string()),
+                Some(Token::Exit) => leaving = Some(Token::Exit.to_string()),
+                // The next statements of the loop are not run either
+                Some(Token::Other(x)) if x == source::BREAK || x == source::CONTINUE => {
+                    leaving = Some(x.to_string());
+                }
+                _ => self.check_unreachable(line_i),
+            }
+        }
+    }
+}
+
+/// If the statement `line_i` always leaves the function : a return or an exit
+/// instruction, a condition with a branch "else" whose branches all leave
+/// the function, or a loop "loop" without "break"
+fn leaves(parsed: &Vec<Vec<Token>>, lines: &Vec<Line>, line_i: usize) -> bool {
+    match parsed[line_i].first() {
+        Some(Token::Return) | Some(Token::Exit) => return true,
+        _ => {}
+    }
+    if let Some(header) = LoopHeader::parse(&lines[line_i].text) {
+        return header.keyword == source::LOOP && !is_broken(parsed, lines, line_i);
+    }
+    if !source::is_header(&parsed[line_i], source::IF) {
+        return false;
+    }
+
+    let branches = source::branches(parsed, lines, line_i);
+    let has_else = source::is_header(&parsed[*branches.last().unwrap()], source::ELSE);
+    has_else && branches.iter().all(| x | block_leaves(parsed, lines, *x))
+}
+
+/// If a "break" instruction leaves the loop started by the line `loop_i`
+fn is_broken(parsed: &Vec<Vec<Token>>, lines: &Vec<Line>, loop_i: usize) -> bool {
+    (loop_i + 1..source::block_end(parsed, lines, loop_i))
+        .filter(| x | source::is_header(&parsed[*x], source::BREAK))
+        .any(| x | source::jump_target(parsed, lines, x) == Some(loop_i))
+}
+
+/// If a statement directly in the block opened by the line `header_i` always
+/// leaves the function
+fn block_leaves(parsed: &Vec<Vec<Token>>, lines: &Vec<Line>, header_i: usize) -> bool {
+    source::block_statements(parsed, lines, header_i)
+        .iter()
+        .any(| x | leaves(parsed, lines, *x))
+}
+
+/// SEE Functions' documentation from `Checker` because they are not
+/// written here a new time
+impl Checker for FlowChecker {
+    fn name(&self) -> &'static str {
+        "flow"
+    }
+
+    fn init(&mut self) {
+
+    }
+
+    /// Only the first token of each line is checked, to find the functions
+    fn check(&mut self) {
+        let parsed = self.data().parsed.clone();
+
+        for line in parsed.iter() {
+            let mut previous_token = Token::None;
+            let mut break_line = false;
+
+            self.data().token_i = 0;
+            if let Some(token) = line.first() {
+                self.check_for_instruction(
+                    line,
+                    &mut break_line,
+                    token,
+                    &mut previous_token,
+                );
+            }
+            self.data().line_i += 1;
+        }
+    }
+
+    fn check_for_instruction(
+        &mut self,
+        _line: &Vec<Token>,
+        break_line: &mut bool,
+        token: &Token,
+        _previous_token: &mut Token
+    ) {
+        match token {
+            Token::Function => {
+                let line_i = self.data().line_i;
+                self.check_function(line_i);
+            }
+            _ => {}
+        }
+        *break_line = true;
+    }
+
+    fn finish(&mut self) -> Result<(), Logger> {
+        self.data().logger.get_result()
+    }
+
+    fn data(&mut self) -> &mut CheckerData {
+        &mut self.data
+    }
+}
@@ -0,0 +1,94 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use jup::tokens::Token;
+
+use unicode_xid::UnicodeXID;
+
+use crate::asm;
+
+/// Keywords of the language that are not in the tokens set, they are
+/// reserved for the statements
+pub const RESERVED_KEYWORDS: [&str; 19] = [
+    "const", "struct", "enum", "println",
+    "if", "elif", "else", "match",
+    "while", "loop", "for", "in", "break", "continue",
+    "true", "false", "and", "or", "not",
+];
+
+/// All the keywords, the ones of the tokens set and the reserved ones \
+/// None of them can be used as an identifier
+pub fn keywords() -> Vec<String> {
+    let mut keywords: Vec<String> = [
+        Token::Function,
+        Token::Return,
+        Token::Static,
+        Token::Variable,
+        Token::Print,
+        Token::Exit,
+    ]
+    .iter()
+    .map(| x | x.to_string())
+    .collect();
+
+    keywords.extend(RESERVED_KEYWORDS.iter().map(| x | x.to_string()));
+    keywords
+}
+
+pub fn is_keyword(word: &str) -> bool {
+    keywords().iter().any(| x | x == word)
+}
+
+/// Check an identifier against the identifiers grammar: a Unicode "XID_Start"
+/// character or "_", followed by "XID_Continue" characters \
+/// An identifier cannot be a keyword or a word reserved by the assembler
+/// (registers, directives). The returned error is the reason
+pub fn check(identifier: &str) -> Result<(), String> {
+    let mut chars = identifier.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_xid_start() => {},
+        Some(c) => return Err(format!(
+            "An identifier cannot start with '{}', only with a letter or '_'", c
+        )),
+        None => return Err("An identifier cannot be empty".to_string()),
+    }
+
+    if let Some(c) = chars.find(| c | !c.is_xid_continue()) {
+        return Err(format!("An identifier cannot contain '{}'", c));
+    }
+
+    if is_keyword(identifier) {
+        return Err(format!("'{}' is a keyword", identifier));
+    }
+
+    let lowercase = identifier.to_lowercase();
+    if asm::is_register(&lowercase) {
+        return Err(format!("'{}' is a register name for the assembler", identifier));
+    }
+    if asm::is_directive(&lowercase) || asm::OPERAND_KEYWORDS.contains(&lowercase.as_str()) {
+        return Err(format!("'{}' is reserved by the assembler", identifier));
+    }
+    Ok(())
+}
+
+/// Transform a name into a symbol name accepted by the assembler, each
+/// non-ASCII character is replaced by "$u" followed by its code point written
+/// with 6 hexadecimal digits, like "$u0000e9" for "é" \
+/// No identifier can contain "$" and the code points have a fixed width, so a
+/// mangled name cannot be the same as another name
+pub fn mangle(name: &str) -> String {
+    let mut mangled = String::new();
+    for c in name.chars() {
+        match c.is_ascii() {
+            true => mangled.push(c),
+            false => mangled += &format!("$u{:06x}", c as u32),
+        }
+    }
+
+    // A symbol starting by "$" is a way to escape a name for the assembler
+    if mangled.starts_with('$') {
+        mangled.insert(0, '_');
+    }
+    mangled
+}
@@ -2,17 +2,118 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+pub mod asm;
+pub mod assembly;
 pub mod base;
 pub mod data;
+pub mod definitions;
+pub mod entry;
+pub mod flow;
+pub mod identifier;
+pub mod registry;
+pub mod source;
+pub mod symbols;
 pub mod syntax;
+pub mod types;
 
+use logging::level::LogLevel;
+use logging::log::Log;
 use logging::logger::Logger;
 
-use crate::base::Checker;
+use crate::registry::Selection;
 
-pub fn run_checkers(data: data::CheckerData) -> Result<(), Logger> {
-    let mut syntax_checker = syntax::SyntaxChecker::new(data);
-    syntax_checker.run()?;
+/// Run all the selected checkers for a source file, each one after its
+/// dependencies \
+/// A checker is not run when one of its dependencies failed, or when it needs
+/// a valid syntax and the syntax checker failed. The required checkers are
+/// run even when they are not selected. The logs of all the run checkers are
+/// returned together, even if there are only warnings
+pub fn run_checkers(
+    data: data::CheckerData,
+    selection: &Selection
+) -> Result<(), Logger> {
+    let mut logger = Logger::new();
 
-    Ok(())
+    let checkers = registry::checkers(&data);
+
+    // Reported once, when checking the first source file
+    let is_first_source = match data.project.sources.first() {
+        Some(first) => first.path == data.source,
+        None => false,
+    };
+    if is_first_source {
+        for name in selection.unknown_names(&checkers) {
+            logger.add_log(
+                Log::new(
+                    LogLevel::Warning,
+                    "Unknown checker".to_string(),
+                    format!("No checker is named '{}', it's ignored", name),
+                )
+                .add_hint(format!(
+                    "Available checkers : {}",
+                    checkers.iter()
+                        .map(| x | x.name())
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ))
+            );
+        }
+    }
+
+    let checkers = match registry::order(checkers) {
+        Ok(checkers) => checkers,
+        Err(names) => {
+            logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Cyclic dependencies".to_string(),
+                    format!(
+                        "The checkers {} depend on each other, none of them \
+                        can be run first",
+                        names.join(", ")
+                    ),
+                )
+                .finish()
+            );
+            return Err(logger);
+        }
+    };
+
+    let mut failed: Vec<&'static str> = vec![];
+    for mut checker in checkers {
+        if !selection.selects(checker.name()) {
+            if !checker.is_required() {
+                continue;
+            }
+
+            if is_first_source {
+                logger.add_log(
+                    Log::new(
+                        LogLevel::Warning,
+                        "Required checker".to_string(),
+                        format!(
+                            "The checker '{}' cannot be skipped, the code \
+                            generation relies on it",
+                            checker.name()
+                        ),
+                    )
+                    .finish()
+                );
+            }
+        }
+
+        let can_run = !checker.depends_on().iter().any(| x | failed.contains(x))
+            && !(checker.needs_valid_syntax() && failed.contains(&syntax::SYNTAX));
+
+        // Its dependents cannot be run either
+        if !can_run || checker.run().is_err() {
+            failed.push(checker.name());
+        }
+        logger.add_logs(&checker.data().logger);
+    }
+
+    match logger.is_empty() {
+        true => Ok(()),
+        false => Err(logger),
+    }
 }
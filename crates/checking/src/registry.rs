@@ -0,0 +1,89 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::{
+    assembly::AssemblyChecker,
+    base::Checker,
+    data::CheckerData,
+    definitions::DefinitionsChecker,
+    entry::EntryChecker,
+    flow::FlowChecker,
+    syntax::SyntaxChecker,
+    types::TypesChecker,
+};
+
+/// All the available checkers for a source file \
+/// A new checker only has to be added here, the pipeline runs it after its
+/// dependencies
+pub fn checkers(data: &CheckerData) -> Vec<Box<dyn Checker>> {
+    vec![
+        Box::new(SyntaxChecker::new(data.clone())),
+        Box::new(FlowChecker::new(data.clone())),
+        Box::new(EntryChecker::new(data.clone())),
+        Box::new(DefinitionsChecker::new(data.clone())),
+        Box::new(AssemblyChecker::new(data.clone())),
+        Box::new(TypesChecker::new(data.clone())),
+    ]
+}
+
+/// Which checkers are run, according to the options "--only-check" and
+/// "--skip-check" \
+/// All of them are run when nothing is selected
+#[derive(Clone, Default)]
+pub struct Selection {
+    pub only: Vec<String>,
+    pub skip: Vec<String>,
+}
+
+impl Selection {
+    pub fn new(only: Vec<String>, skip: Vec<String>) -> Self {
+        Self {
+            only,
+            skip,
+        }
+    }
+
+    pub fn selects(&self, name: &str) -> bool {
+        if self.skip.iter().any(| x | x == name) {
+            return false;
+        }
+        self.only.is_empty() || self.only.iter().any(| x | x == name)
+    }
+
+    /// All the selected names that match with no checker
+    pub fn unknown_names(&self, checkers: &Vec<Box<dyn Checker>>) -> Vec<String> {
+        self.only.iter()
+            .chain(self.skip.iter())
+            .filter(| name | !checkers.iter().any(| x | x.name() == name.as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Sort the checkers to place each one after its dependencies, the
+/// registration order is kept otherwise \
+/// Gives the names of the checkers that cannot be placed when their
+/// dependencies are cyclic
+pub fn order(
+    mut checkers: Vec<Box<dyn Checker>>
+) -> Result<Vec<Box<dyn Checker>>, Vec<&'static str>> {
+    let mut ordered: Vec<Box<dyn Checker>> = vec![];
+
+    while !checkers.is_empty() {
+        let ready_i = checkers.iter()
+            .position(| checker | {
+                checker.depends_on().iter().all(| dependency | {
+                    // An unknown dependency is ignored
+                    ordered.iter().any(| x | x.name() == *dependency)
+                        || !checkers.iter().any(| x | x.name() == *dependency)
+                })
+            });
+
+        match ready_i {
+            Some(ready_i) => ordered.push(checkers.remove(ready_i)),
+            None => return Err(checkers.iter().map(| x | x.name()).collect()),
+        }
+    }
+    Ok(ordered)
+}
@@ -0,0 +1,368 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use std::fs;
+
+use jup::tokens::Token;
+
+use objects::{
+    expression::Expression,
+    function::Signature,
+};
+
+/// Information about a source line that the parser does not keep: its
+/// indentation and its code as written (without the comment)
+#[derive(Clone, Default, Debug)]
+pub struct Line {
+    pub indent: usize,
+    pub text: String,
+}
+
+impl Line {
+    pub fn from_raw(raw: &str) -> Self {
+        let mut indent = 0;
+        for c in raw.chars() {
+            match c {
+                ' ' => indent += 1,
+                '\t' => indent += 4,
+                _ => break,
+            }
+        }
+
+        Self {
+            indent,
+            text: strip_comment(raw).trim().to_string(),
+        }
+    }
+
+    /// The code after the first word of the line, which is the instruction
+    /// keyword \
+    /// Example: "let x: int = 5" gives "x: int = 5"
+    pub fn rest(&self) -> &str {
+        match self.text.split_once(char::is_whitespace) {
+            Some((_, rest)) => rest.trim(),
+            None => "",
+        }
+    }
+}
+
+/// Remove the comment at the end of a raw line, if there is one \
+/// A comment delimiter placed in a string is not a comment, the label of a
+/// loop does not start a string
+pub fn strip_comment(raw: &str) -> &str {
+    let mut in_string: Option<char> = None;
+    let mut previous = '\0';
+
+    for (i, c) in raw.char_indices() {
+        match in_string {
+            Some(delimiter) => {
+                if c == delimiter && previous != '\\' {
+                    in_string = None;
+                }
+            }
+            None => match c {
+                LABEL if is_label_start(&raw[..i]) => {}
+                '"' | '\'' => in_string = Some(c),
+                '/' if previous == '/' => return &raw[..i - 1],
+                _ => {}
+            },
+        }
+        previous = c;
+    }
+    raw
+}
+
+/// If a label starts after `before` : at the start of the line or after a
+/// jump instruction
+fn is_label_start(before: &str) -> bool {
+    matches!(before.trim(), "" | BREAK | CONTINUE)
+}
+
+/// Read the source file and get a `Line` for each line of `parsed` \
+/// When the parser did not keep the lines without code, the lines with code
+/// are matched in order
+pub fn lines(source: &str, parsed: &Vec<Vec<Token>>) -> Vec<Line> {
+    let content = fs::read_to_string(source).unwrap_or_default();
+    let raw_lines: Vec<Line> = content.lines().map(Line::from_raw).collect();
+
+    if raw_lines.len() == parsed.len() {
+        return raw_lines;
+    }
+
+    let mut code_lines = raw_lines.into_iter().filter(|line| !line.text.is_empty());
+    parsed
+        .iter()
+        .map(|tokens| match tokens.is_empty() {
+            true => Line::default(),
+            false => code_lines.next().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Keyword of a structure declaration, "struct <id>" followed by a block of
+/// fields written "<id>: <type>"
+pub const STRUCT: &str = "struct";
+/// Keyword of an enum declaration, written on one line :
+/// "enum <id>: <type> = <variant> | <variant> = <value> | ..."
+pub const ENUM: &str = "enum";
+/// Keyword of a constant declaration, "const <id>: <type> = <value>" where the
+/// value is computed at compile time
+pub const CONST: &str = "const";
+/// Keyword of a match statement, "match <expression>" followed by a block of
+/// arms, each one is a line of patterns followed by the block of its body
+pub const MATCH: &str = "match";
+/// Keyword of a loop, "while <condition>" followed by the block run again
+/// while the condition is true
+pub const WHILE: &str = "while";
+/// Keyword of an infinite loop, "loop" followed by the block run again until a
+/// "break" or a return instruction
+pub const LOOP: &str = "loop";
+/// Keyword of a counted loop, "for <id> in <start>..<end>" followed by the
+/// block run for each value of the counter, SEE `objects::range`
+pub const FOR: &str = "for";
+/// Instruction leaving the innermost loop, or the loop named by its label :
+/// "break" or "break '<label>"
+pub const BREAK: &str = "break";
+/// Instruction starting the next iteration of the innermost loop, or of the
+/// loop named by its label : "continue" or "continue '<label>"
+pub const CONTINUE: &str = "continue";
+/// Keyword of the instruction writing a message like "print", followed by a
+/// new line : "println <message>"
+pub const PRINTLN: &str = "println";
+/// Character starting the label of a loop, "'<label>: loop"
+pub const LABEL: char = '\'';
+
+/// Keyword of a condition, "if <condition>" followed by the block run when
+/// the condition is true
+pub const IF: &str = "if";
+/// Keyword of a branch following a condition, "elif <condition>" followed by
+/// the block run when the previous conditions are false and this one is true
+pub const ELIF: &str = "elif";
+/// Pattern of the arm matching all the values not matched by the other arms,
+/// and last branch of a condition run when all its conditions are false
+pub const ELSE: &str = "else";
+
+/// If the line starts with the keyword `keyword`, like the header of a
+/// structure declaration with `STRUCT`
+pub fn is_header(line: &Vec<Token>, keyword: &str) -> bool {
+    matches!(line.first(), Some(Token::Other(first)) if first == keyword)
+}
+
+/// If the line is a branch of a condition : "if", "elif" or "else"
+pub fn is_branch(line: &Vec<Token>) -> bool {
+    is_header(line, IF) || is_header(line, ELIF) || is_header(line, ELSE)
+}
+
+/// If the line is a call of a function used as a statement, like
+/// "add(1, x)" or "lib.add(1, x)" \
+/// Only the name followed by parentheses is looked at, the arguments are
+/// checked by "syntax"
+pub fn is_call(text: &Line) -> bool {
+    let text = text.text.trim_end();
+    let name = match text.find('(') {
+        Some(open) if text.ends_with(')') => text[..open].trim_end(),
+        _ => return false,
+    };
+
+    !name.is_empty() && name.split('.').all(| x | {
+        x.chars().next().map_or(false, | c | c.is_alphabetic() || c == '_')
+            && x.chars().all(| c | c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Identifier of the function declared by the line, `None` when it's not a
+/// function header \
+/// Example: "func add(a: int, b: int): int" gives "add"
+pub fn function_id(line: &Vec<Token>, text: &Line) -> Option<String> {
+    if !matches!(line.first(), Some(Token::Function)) {
+        return None;
+    }
+
+    match Signature::parse(text.rest()) {
+        Ok(signature) => Some(signature.id),
+        Err(_) => Some(text.rest().split(| c: char | !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default()
+            .to_string()),
+    }
+}
+
+/// Patterns of a match arm, written "<pattern>, <pattern>, ..." \
+/// `None` for the arm "else"
+pub fn arm_patterns(text: &str) -> Result<Option<Vec<Expression>>, String> {
+    if text == ELSE {
+        return Ok(None);
+    }
+
+    text.split(',')
+        .map(| pattern | Expression::parse(pattern.trim()))
+        .collect::<Result<Vec<Expression>, String>>()
+        .map(Some)
+}
+
+/// Index of the first line after the block opened by the line `header_i` \
+/// The block is made of all the following lines more indented than its
+/// header, the lines without code are ignored
+pub fn block_end(parsed: &Vec<Vec<Token>>, lines: &Vec<Line>, header_i: usize) -> usize {
+    let indent = lines[header_i].indent;
+
+    let mut end = header_i + 1;
+    for line_i in header_i + 1..parsed.len() {
+        if parsed[line_i].is_empty() {
+            continue;
+        }
+        if lines[line_i].indent <= indent {
+            break;
+        }
+        end = line_i + 1;
+    }
+    end
+}
+
+/// Indexes of the statements directly in the block opened by the line
+/// `header_i`, the lines of the nested blocks are not included
+pub fn block_statements(
+    parsed: &Vec<Vec<Token>>,
+    lines: &Vec<Line>,
+    header_i: usize,
+) -> Vec<usize> {
+    let end = block_end(parsed, lines, header_i);
+
+    let mut statements = vec![];
+    let mut line_i = header_i + 1;
+    while line_i < end {
+        if parsed[line_i].is_empty() {
+            line_i += 1;
+            continue;
+        }
+        statements.push(line_i);
+        line_i = block_end(parsed, lines, line_i);
+    }
+    statements
+}
+
+/// Index of the branch "elif" or "else" following the branch opened by the
+/// line `header_i`, when the next line of code has the same indentation
+pub fn next_branch(
+    parsed: &Vec<Vec<Token>>,
+    lines: &Vec<Line>,
+    header_i: usize,
+) -> Option<usize> {
+    let next_i = (block_end(parsed, lines, header_i)..parsed.len())
+        .find(| x | !parsed[*x].is_empty())?;
+
+    let is_next_branch = lines[next_i].indent == lines[header_i].indent
+        && (is_header(&parsed[next_i], ELIF) || is_header(&parsed[next_i], ELSE));
+    match is_next_branch {
+        true => Some(next_i),
+        false => None,
+    }
+}
+
+/// Indexes of the branches of the condition started by the line `if_i` : the
+/// line "if" followed by its lines "elif" and "else"
+pub fn branches(parsed: &Vec<Vec<Token>>, lines: &Vec<Line>, if_i: usize) -> Vec<usize> {
+    let mut branches = vec![if_i];
+    while let Some(next_i) = next_branch(parsed, lines, *branches.last().unwrap()) {
+        branches.push(next_i);
+        if is_header(&parsed[next_i], ELSE) {
+            break;
+        }
+    }
+    branches
+}
+
+/// Header of a loop : "while <condition>", "loop" or "for <range>", preceded
+/// by its label "'<label>:" when it has one
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoopHeader {
+    pub label: Option<String>,
+    /// Keyword after the label, not a loop keyword for an invalid header
+    pub keyword: String,
+    /// Empty for "loop", the range of "for"
+    pub condition: String,
+}
+
+impl LoopHeader {
+    /// `None` when the line is not a loop and has no label \
+    /// Example: "'outer: while i < 10" gives the label "outer", the keyword
+    /// "while" and the condition "i < 10"
+    pub fn parse(text: &str) -> Option<Self> {
+        let (label, code) = match text.strip_prefix(LABEL) {
+            Some(labeled) => match labeled.split_once(':') {
+                Some((label, code)) => (Some(label.trim().to_string()), code.trim()),
+                None => (Some(labeled.trim().to_string()), ""),
+            }
+            None => (None, text),
+        };
+
+        let (keyword, condition) = match code.split_once(char::is_whitespace) {
+            Some((keyword, condition)) => (keyword, condition.trim()),
+            None => (code, ""),
+        };
+
+        if label.is_none() && keyword != WHILE && keyword != LOOP && keyword != FOR {
+            return None;
+        }
+
+        Some(Self {
+            label,
+            keyword: keyword.to_string(),
+            condition: condition.to_string(),
+        })
+    }
+}
+
+/// Label written after a jump instruction "break" or "continue", `Err` when
+/// the code after the instruction is not a label
+pub fn jump_label(text: &Line) -> Result<Option<String>, String> {
+    match text.rest() {
+        "" => Ok(None),
+        rest => match rest.strip_prefix(LABEL) {
+            Some(label) => Ok(Some(label.to_string())),
+            None => Err(format!(
+                "Expected the label of a loop like '{}<label>' but found '{}'",
+                LABEL,
+                rest
+            )),
+        }
+    }
+}
+
+/// Indexes of the headers of the blocks containing the line `line_i`, from
+/// the innermost one
+pub fn enclosing_headers(
+    parsed: &Vec<Vec<Token>>,
+    lines: &Vec<Line>,
+    line_i: usize,
+) -> Vec<usize> {
+    let mut headers = vec![];
+    let mut indent = lines[line_i].indent;
+
+    for header_i in (0..line_i).rev() {
+        if indent == 0 {
+            break;
+        }
+        if parsed[header_i].is_empty() || lines[header_i].indent >= indent {
+            continue;
+        }
+        headers.push(header_i);
+        indent = lines[header_i].indent;
+    }
+    headers
+}
+
+/// Index of the header of the loop left or continued by the jump instruction
+/// of the line `line_i` : the innermost loop, or the loop with its label \
+/// `None` when the line is not in a loop or no loop has the label
+pub fn jump_target(parsed: &Vec<Vec<Token>>, lines: &Vec<Line>, line_i: usize) -> Option<usize> {
+    let label = jump_label(&lines[line_i]).ok()?;
+
+    enclosing_headers(parsed, lines, line_i)
+        .into_iter()
+        .find(| x | match LoopHeader::parse(&lines[*x].text) {
+            Some(header) => label.is_none() || header.label == label,
+            None => false,
+        })
+}
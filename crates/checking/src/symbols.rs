@@ -0,0 +1,150 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::identifier;
+
+use objects::{
+    constant::Constant,
+    enumeration::Enum,
+    function::Function,
+    structure::Struct,
+    variable::Variable,
+};
+
+/// What is defined by a symbol
+#[derive(Clone)]
+pub enum SymbolKind {
+    Function(Function),
+    Static(Variable),
+    /// `None` when its layout cannot be computed because a field has an
+    /// unknown type or contains the structure itself, reported by "types"
+    Struct(Option<Struct>),
+    /// Only valid declarations are symbols, the others are reported by
+    /// "syntax"
+    Enum(Enum),
+    Const(Constant),
+}
+
+/// A function, a static variable, a structure, an enum or a constant defined in a source file of
+/// the project
+#[derive(Clone)]
+pub struct Symbol {
+    /// Scoped name, it's also the label in the generated assembly
+    pub id: String,
+    pub module: String,
+    pub source: String,
+    pub line_i: usize,
+    pub kind: SymbolKind,
+}
+
+/// Result of a symbol reference resolution
+pub enum Resolution {
+    Found(Symbol),
+    /// Many symbols can be referenced by the same name
+    Ambiguous(Vec<Symbol>),
+    Unresolved,
+}
+
+/// All the symbols defined in the project, built after having parsed all the
+/// source files and before generating any code
+#[derive(Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self {
+            symbols: vec![],
+        }
+    }
+
+    pub fn add(&mut self, symbol: Symbol) {
+        self.symbols.push(symbol);
+    }
+
+    pub fn symbols(&self) -> &Vec<Symbol> {
+        &self.symbols
+    }
+
+    /// All the symbols with this scoped name, more than one means it's
+    /// defined multiple times
+    pub fn get(&self, id: &str) -> Vec<Symbol> {
+        self.symbols.iter()
+            .filter(| x | x.id == id)
+            .cloned()
+            .collect()
+    }
+
+    /// Find the symbol referenced by `name` from the module `module`, in this
+    /// order:
+    /// - the complete scoped name, like "test.exit"
+    /// - a name of the module itself, like "exit" in the module "test"
+    /// - a name of another module, like "exit" for "test.exit" in the module
+    /// "main", only when one module defines it
+    pub fn resolve(&self, name: &str, module: &str) -> Resolution {
+        // The scoped names are mangled, SEE `identifier::mangle()`
+        let name = &identifier::mangle(name);
+        let module = &identifier::mangle(module);
+
+        let candidates = self.get(name);
+        if !candidates.is_empty() {
+            return Self::to_resolution(candidates);
+        }
+
+        let candidates = self.get(&format!("{}.{}", module, name));
+        if !candidates.is_empty() {
+            return Self::to_resolution(candidates);
+        }
+
+        let candidates: Vec<Symbol> = self.symbols.iter()
+            .filter(| x | x.id == format!("{}.{}", x.module, name))
+            .cloned()
+            .collect();
+        Self::to_resolution(candidates)
+    }
+
+    /// The structure referenced by `name` from the module `module`, when
+    /// its layout is known
+    pub fn struct_(&self, name: &str, module: &str) -> Option<Struct> {
+        match self.resolve(name, module) {
+            Resolution::Found(Symbol { kind: SymbolKind::Struct(struct_), .. }) => struct_,
+            _ => None,
+        }
+    }
+
+    /// The enum referenced by `name` from the module `module`
+    pub fn enum_(&self, name: &str, module: &str) -> Option<Enum> {
+        match self.resolve(name, module) {
+            Resolution::Found(Symbol { kind: SymbolKind::Enum(enum_), .. }) => Some(enum_),
+            _ => None,
+        }
+    }
+
+    /// The constant referenced by `name` from the module `module`
+    pub fn constant(&self, name: &str, module: &str) -> Option<Constant> {
+        match self.resolve(name, module) {
+            Resolution::Found(Symbol { kind: SymbolKind::Const(constant), .. }) => Some(constant),
+            _ => None,
+        }
+    }
+
+    /// The function referenced by `name` from the module `module`
+    pub fn function(&self, name: &str, module: &str) -> Option<Function> {
+        match self.resolve(name, module) {
+            Resolution::Found(Symbol { kind: SymbolKind::Function(function), .. }) => {
+                Some(function)
+            }
+            _ => None,
+        }
+    }
+
+    fn to_resolution(mut candidates: Vec<Symbol>) -> Resolution {
+        match candidates.len() {
+            0 => Resolution::Unresolved,
+            1 => Resolution::Found(candidates.remove(0)),
+            _ => Resolution::Ambiguous(candidates),
+        }
+    }
+}
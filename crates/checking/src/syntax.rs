@@ -11,23 +11,770 @@ use logging::logger::Logger;
 
 use crate::base::Checker;
 use crate::data::CheckerData;
+use crate::identifier;
+use crate::source;
+
+use objects::{
+    enumeration::Enum,
+    expression::{split_assignment, Expression},
+    format::Format,
+    function::Signature,
+    range::{self, Range},
+    variable::Definition,
+};
+
+/// Name of the syntax checker, the checkers needing a valid syntax are only
+/// run when it passed
+pub const SYNTAX: &str = "syntax";
 
 /// Check syntax of a given source file content
 pub struct SyntaxChecker {
     data: CheckerData, 
+    /// First line after the structure being checked, its fields are checked
+    /// with its header
+    struct_end: usize,
+    /// Lines of patterns of the match statements, checked with their header
+    arm_lines: Vec<usize>,
+    /// Lines "elif" and "else" of the conditions, checked with their "if"
+    branch_lines: Vec<usize>,
 }
 
 impl SyntaxChecker {
     pub fn new(data: CheckerData) -> Self {
         Self {
             data,
+            struct_end: 0,
+            arm_lines: vec![],
+            branch_lines: vec![],
+        }
+    }
+
+    /// Check the identifier defined by the line, `what` is the kind of the
+    /// defined object \
+    /// Return if the identifier is valid
+    fn check_identifier(
+        &mut self,
+        line: &Vec<Token>,
+        token: &Token,
+        what: &str,
+        cause: String
+    ) -> bool {
+        let reason = match token {
+            Token::Other(id) => match identifier::check(id) {
+                Ok(()) => return true,
+                Err(reason) => reason,
+            }
+            _ => format!("'{}' is a keyword", token.to_string()),
+        };
+
+        let token_i: usize = self.data().token_i;
+        self.data().logger.add_log(
+            Log::new(
+                LogLevel::Error,
+                format!("Invalid identifier for {}", what),
+                format!(
+                    "{}Found '{}' but it cannot be used as a {} identifier",
+                    line_to_string(line, token_i + 1),
+                    token.to_string(),
+                    what
+                )
+            )
+            .add_cause(cause)
+            .add_hint(reason)
+        );
+        false
+    }
+
+    /// Check a function header : "func <id>(<param>: <type>, ...): <type>",
+    /// the function and its parameters need valid identifiers
+    fn check_function(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+
+        let reason = match Signature::parse(self.data().lines[line_i].rest()) {
+            Ok(signature) => std::iter::once(&signature.id)
+                .chain(signature.params.iter().map(| x | &x.id))
+                .find_map(| x | identifier::check(x).err()),
+            Err(reason) => Some(reason),
+        };
+
+        if let Some(reason) = reason {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid function".to_string(),
+                    format!(
+                        "{}The function header is not a valid declaration",
+                        line_to_string(line, 1)
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(format!(
+                    "{}, declare a function with '{} <id>(<param>: <type>, ...)'",
+                    reason,
+                    Token::Function.to_string()
+                ))
+            );
+        }
+    }
+
+    /// Check the expression given as value to the variable defined by the
+    /// line, if there is one
+    fn check_value(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+        let definition = Definition::parse(self.data().lines[line_i].rest());
+
+        let value = match definition.value {
+            Some(value) => value,
+            None => return,
+        };
+
+        if let Err(reason) = Expression::parse(&value) {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid expression".to_string(),
+                    format!(
+                        "{}The value of '{}' is not a valid expression",
+                        line_to_string(line, 0),
+                        definition.id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check the value returned by the line, if there is one
+    fn check_return(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+        let value = self.data().lines[line_i].rest().to_string();
+
+        if value.is_empty() {
+            return;
+        }
+
+        if let Err(reason) = Expression::parse(&value) {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid expression".to_string(),
+                    format!(
+                        "{}The returned value is not a valid expression",
+                        line_to_string(line, 1)
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check a line like "<target> = <value>", the target has to be
+    /// something that can be assigned
+    fn check_assignment(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+        let text = self.data().lines[line_i].text.clone();
+        let (target, value) = split_assignment(&text).unwrap();
+
+        let reason = match Expression::parse(target) {
+            Ok(target) if target.is_assignable() => None,
+            Ok(target) => Some(format!(
+                "'{}' is not a variable, an element, a field or a pointed value",
+                target
+            )),
+            Err(reason) => Some(reason),
+        };
+
+        if let Some(reason) = reason {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid assignment".to_string(),
+                    format!(
+                        "{}Nothing can be assigned to '{}'",
+                        line_to_string(line, 0),
+                        target
+                    )
+                )
+                .add_cause(cause.clone())
+                .add_hint(reason)
+            );
+        }
+
+        if let Err(reason) = Expression::parse(value) {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid expression".to_string(),
+                    format!(
+                        "{}The value assigned to '{}' is not a valid expression",
+                        line_to_string(line, 0),
+                        target
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check a line like "<function>(<arg>, ...)", a call of a function used
+    /// as a statement
+    fn check_call(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+        let text = self.data().lines[line_i].text.clone();
+
+        let reason = match Expression::parse(&text) {
+            Ok(Expression::Call(_, _)) => return,
+            Ok(expression) => format!(
+                "'{}' is not a call of a function, its value would be lost",
+                expression
+            ),
+            Err(reason) => reason,
+        };
+
+        self.data().logger.add_log(
+            Log::new(
+                LogLevel::Error,
+                "Invalid call".to_string(),
+                format!(
+                    "{}This line is not a valid call of a function",
+                    line_to_string(line, 0)
+                )
+            )
+            .add_cause(cause)
+            .add_hint(reason)
+        );
+    }
+
+    /// Check the message written by "print" or "println" : a value, or a
+    /// string literal where values are inserted, SEE `objects::format` \
+    /// Nothing is written when the message is missing
+    fn check_print(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+        let message = self.data().lines[line_i].rest().to_string();
+        if message.is_empty() {
+            return;
+        }
+
+        if let Err(reason) = Format::parse(&message) {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid print".to_string(),
+                    format!(
+                        "{}The printed message is not valid",
+                        line_to_string(line, 1)
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check a structure declaration : "struct <id>" at the top level of the
+    /// source file, followed by a block of fields written "<id>: <type>"
+    fn check_struct(&mut self, line: &Vec<Token>, cause: String) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        self.struct_end = source::block_end(&parsed, &lines, line_i);
+
+        if lines[line_i].indent != 0 {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid structure".to_string(),
+                    format!(
+                        "{}A structure is declared at the top level of a source file",
+                        line_to_string(line, 0)
+                    )
+                )
+                .add_cause(cause.clone())
+                .finish()
+            );
+        }
+
+        match line.get(1) {
+            Some(token) if line.len() == 2 => {
+                if !self.check_identifier(line, token, "structure", cause.clone()) {
+                    return;
+                }
+            }
+            _ => {
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid structure".to_string(),
+                        format!(
+                            "{}Expected a name after '{}' and nothing else",
+                            line_to_string(line, 1),
+                            source::STRUCT
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!("Declare a structure with '{} <id>'", source::STRUCT))
+                );
+                return;
+            }
+        }
+
+        let fields = source::block_statements(&parsed, &lines, line_i);
+        if fields.is_empty() {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Empty structure".to_string(),
+                    format!(
+                        "{}The structure '{}' has no field",
+                        line_to_string(line, 2),
+                        line[1].to_string()
+                    )
+                )
+                .add_cause(cause)
+                .add_hint("Declare its fields in the block, like '<id>: <type>'".to_string())
+            );
+            return;
+        }
+
+        let mut ids: Vec<String> = vec![];
+        for field_i in fields {
+            let definition = Definition::parse(&lines[field_i].text);
+
+            let reason = if let Err(reason) = identifier::check(&definition.id) {
+                Some(reason)
+            } else if ids.contains(&definition.id) {
+                Some(format!("The field '{}' is already declared", definition.id))
+            } else if definition.type_.is_none() {
+                Some(format!(
+                    "Specify the field's type with '{}' + <type>",
+                    &Token::TypeDef.to_string()
+                ))
+            } else if definition.value.is_some() {
+                Some("A field has no default value".to_string())
+            } else if source::block_end(&parsed, &lines, field_i) > field_i + 1 {
+                Some("A field cannot open a block".to_string())
+            } else {
+                None
+            };
+
+            if let Some(reason) = reason {
+                let cause = source_to_string(self.data().source.clone(), field_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid field".to_string(),
+                        format!(
+                            "{}This line is not a valid field of '{}'",
+                            line_to_string(&parsed[field_i], 0),
+                            line[1].to_string()
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+            }
+            ids.push(definition.id);
+        }
+    }
+
+    /// Check an enum declaration : "enum <id>: <type> = <variant> | ..." at
+    /// the top level of the source file
+    fn check_enum(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+
+        if self.data().lines[line_i].indent != 0 {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid enum".to_string(),
+                    format!(
+                        "{}An enum is declared at the top level of a source file",
+                        line_to_string(line, 0)
+                    )
+                )
+                .add_cause(cause.clone())
+                .finish()
+            );
+        }
+
+        match line.get(1) {
+            Some(token) => {
+                if !self.check_identifier(line, token, "enum", cause.clone()) {
+                    return;
+                }
+            }
+            None => {
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Expected token".to_string(),
+                        format!(
+                            "{}No token was found next to '{}' but expected",
+                            line_to_string(line, 1),
+                            source::ENUM
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!(
+                        "Declare an enum with '{} <id> = <variant> | <variant>'",
+                        source::ENUM
+                    ))
+                );
+                return;
+            }
+        }
+
+        let reason = match Enum::parse(String::new(), self.data().lines[line_i].rest()) {
+            Ok(enum_) => enum_.variants.iter()
+                .find_map(| (variant, _) | identifier::check(variant).err()),
+            Err(reason) => Some(reason),
+        };
+
+        if let Some(reason) = reason {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid enum".to_string(),
+                    format!(
+                        "{}The enum '{}' is not a valid declaration",
+                        line_to_string(line, 2),
+                        line[1].to_string()
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check a constant declaration : "const <id>: <type> = <value>" at the
+    /// top level of the source file, the type and the value are required
+    fn check_const(&mut self, line: &Vec<Token>, cause: String) {
+        let line_i = self.data().line_i;
+        let definition = Definition::parse(self.data().lines[line_i].rest());
+
+        let reason = if self.data().lines[line_i].indent != 0 {
+            Some("A constant is declared at the top level of a source file".to_string())
+        } else if let Err(reason) = identifier::check(&definition.id) {
+            Some(reason)
+        } else if definition.type_.is_none() {
+            Some(format!(
+                "Specify the constant's type with '{}' + <type>",
+                &Token::TypeDef.to_string()
+            ))
+        } else if definition.value.is_none() {
+            Some(format!("Give the constant's value with '{}' + <value>", Token::Assign.to_string()))
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid constant".to_string(),
+                    format!(
+                        "{}This line is not a valid constant declaration",
+                        line_to_string(line, 0)
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+            return;
+        }
+
+        self.check_value(line, cause);
+    }
+
+    /// Check a match statement : "match <expression>" followed by a block of
+    /// arms, each one is a line of patterns opening the block of its body \
+    /// The arm "else" can only be the last one
+    fn check_match(&mut self, line: &Vec<Token>, cause: String) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        if let Err(reason) = Expression::parse(lines[line_i].rest()) {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid expression".to_string(),
+                    format!(
+                        "{}The matched value is not a valid expression",
+                        line_to_string(line, 2)
+                    )
+                )
+                .add_cause(cause.clone())
+                .add_hint(reason)
+            );
+        }
+
+        let arms = source::block_statements(&parsed, &lines, line_i);
+        if arms.is_empty() {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Empty match".to_string(),
+                    format!("{}The match has no arm", line_to_string(line, 0))
+                )
+                .add_cause(cause)
+                .add_hint(format!(
+                    "Write its arms in the block, like '<pattern>, <pattern>' or '{}' \
+                    followed by the block of their body",
+                    source::ELSE
+                ))
+            );
+            return;
+        }
+
+        for (i, arm_i) in arms.iter().enumerate() {
+            self.arm_lines.push(*arm_i);
+
+            let reason = match source::arm_patterns(&lines[*arm_i].text) {
+                Err(reason) => Some(reason),
+                Ok(None) if i + 1 < arms.len() => Some(format!(
+                    "The arm '{}' is the last one, the arms after it are never reached",
+                    source::ELSE
+                )),
+                Ok(_) if source::block_statements(&parsed, &lines, *arm_i).is_empty() => {
+                    Some("An arm opens the block of its body".to_string())
+                }
+                Ok(_) => None,
+            };
+
+            if let Some(reason) = reason {
+                let cause = source_to_string(self.data().source.clone(), *arm_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid arm".to_string(),
+                        format!(
+                            "{}This line is not a valid arm of the match",
+                            line_to_string(&parsed[*arm_i], 0)
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+            }
+        }
+    }
+
+    /// Check a condition : "if <condition>" followed by the lines
+    /// "elif <condition>" and the line "else" of the same indentation, each
+    /// branch opens the block of its body
+    fn check_condition(&mut self, cause: String) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        for branch_i in source::branches(&parsed, &lines, line_i) {
+            if branch_i != line_i {
+                self.branch_lines.push(branch_i);
+            }
+
+            let keyword = lines[branch_i].text.split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let condition = lines[branch_i].rest();
+
+            let reason = match (keyword == source::ELSE, condition.is_empty()) {
+                (true, false) => Some(format!(
+                    "The branch '{}' has no condition, write '{} <condition>' instead",
+                    source::ELSE,
+                    source::ELIF
+                )),
+                (false, true) => Some(format!("Write the condition after '{}'", keyword)),
+                (false, false) => Expression::parse(condition).err(),
+                (true, true) => None,
+            };
+            let reason = reason.or_else(|| {
+                match source::block_statements(&parsed, &lines, branch_i).is_empty() {
+                    true => Some("A branch opens the block of its body".to_string()),
+                    false => None,
+                }
+            });
+
+            if let Some(reason) = reason {
+                let cause = match branch_i == line_i {
+                    true => cause.clone(),
+                    false => source_to_string(self.data().source.clone(), branch_i, 0),
+                };
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid branch".to_string(),
+                        format!(
+                            "{}This line is not a valid branch '{}' of the condition",
+                            line_to_string(&parsed[branch_i], 0),
+                            keyword
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+            }
+        }
+    }
+
+    /// Check a loop header : "while <condition>", "loop" or "for <range>",
+    /// preceded by "'<label>:" to name it, the loops containing it have other
+    /// labels
+    fn check_loop(&mut self, line: &Vec<Token>, cause: String) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        let header = source::LoopHeader::parse(&lines[line_i].text)
+            .unwrap(); // it's a loop header
+        let is_used = | label: &String | source::enclosing_headers(&parsed, &lines, line_i)
+            .iter()
+            .filter_map(| x | source::LoopHeader::parse(&lines[*x].text))
+            .any(| x | x.label.as_ref() == Some(label));
+
+        let reason = match header.label {
+            Some(ref label) => match identifier::check(label) {
+                Err(reason) => Some(reason),
+                Ok(()) if is_used(label) => Some(format!(
+                    "The label '{}' is already used by a loop containing this one",
+                    label
+                )),
+                Ok(()) => None,
+            }
+            None => None,
+        };
+
+        let reason = reason.or_else(|| match header.keyword.as_str() {
+            source::WHILE if header.condition.is_empty() => {
+                Some(format!("Write the condition after '{}'", source::WHILE))
+            }
+            source::WHILE => Expression::parse(&header.condition).err(),
+            source::LOOP if !header.condition.is_empty() => Some(format!(
+                "The loop '{}' has no condition, write '{} <condition>' instead",
+                source::LOOP,
+                source::WHILE
+            )),
+            source::LOOP => None,
+            source::FOR => match Range::parse(&header.condition) {
+                Ok(range) => identifier::check(&range.counter).err(),
+                Err(reason) => Some(format!(
+                    "{}, write '{} <id> in <start>..<end> {} <step>'",
+                    reason,
+                    source::FOR,
+                    range::STEP
+                )),
+            }
+            _ => Some(format!(
+                "Only a loop has a label, write '{}<label>: {}' or '{}<label>: {} <condition>'",
+                source::LABEL,
+                source::LOOP,
+                source::LABEL,
+                source::WHILE
+            )),
+        });
+
+        let reason = reason.or_else(|| {
+            match source::block_statements(&parsed, &lines, line_i).is_empty() {
+                true => Some("A loop opens the block of its body".to_string()),
+                false => None,
+            }
+        });
+
+        if let Some(reason) = reason {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid loop".to_string(),
+                    format!(
+                        "{}This line is not a valid loop header",
+                        line_to_string(line, 0)
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
         }
     }
+
+    /// Check a jump instruction "break" or "continue", it's placed in a loop
+    /// and its label is the label of a loop containing it
+    fn check_jump(&mut self, line: &Vec<Token>, cause: String) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        let keyword = line[0].to_string();
+        let reason = match source::jump_label(&lines[line_i]) {
+            Err(reason) => Some(reason),
+            Ok(_) if source::jump_target(&parsed, &lines, line_i).is_some() => None,
+            Ok(None) => Some(format!("'{}' is only used in a loop", keyword)),
+            Ok(Some(label)) => Some(format!(
+                "No loop containing this line has the label '{}'",
+                label
+            )),
+        };
+
+        if let Some(reason) = reason {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid jump".to_string(),
+                    format!(
+                        "{}This '{}' has no loop to jump to",
+                        line_to_string(line, 1),
+                        keyword
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// A line "elif" or "else" which does not follow a branch of a condition
+    /// with the same indentation
+    fn check_lonely_branch(&mut self, line: &Vec<Token>, cause: String) {
+        self.data().logger.add_log(
+            Log::new(
+                LogLevel::Error,
+                "Invalid branch".to_string(),
+                format!(
+                    "{}This branch does not follow a condition",
+                    line_to_string(line, 0)
+                )
+            )
+            .add_cause(cause)
+            .add_hint(format!(
+                "Write it after the block of a branch '{}' or '{}' with the same \
+                indentation",
+                source::IF,
+                source::ELIF
+            ))
+        );
+    }
 }
 
 /// SEE Functions' documentation from `Checker` because they are not
 /// written here a new time
 impl Checker for SyntaxChecker {
+    fn name(&self) -> &'static str {
+        SYNTAX
+    }
+
+    fn needs_valid_syntax(&self) -> bool {
+        false
+    }
+
+    fn is_required(&self) -> bool {
+        true
+    }
+
     fn init(&mut self) {
 
     }
@@ -36,6 +783,15 @@ impl Checker for SyntaxChecker {
         let parsed = self.data().parsed.clone();
 
         for line in parsed.iter() {
+            // Checked with the header of their structure, match or condition
+            let line_i = self.data().line_i;
+            let is_checked = self.arm_lines.contains(&line_i)
+                || self.branch_lines.contains(&line_i);
+            if line_i < self.struct_end || is_checked {
+                self.data().line_i += 1;
+                continue;
+            }
+
             let mut previous_token = Token::None;
             let mut break_line = false; // to break the loop from the closure
 
@@ -81,45 +837,28 @@ impl Checker for SyntaxChecker {
                 *break_line = true;
             },
             Token::Assign => {},
-            Token::Function => {},
-            Token::Return => {},
+            Token::Function => {
+                self.check_function(line, cause);
+                *break_line = true;
+            }
+            Token::Return => {
+                self.check_return(line, cause);
+                *break_line = true;
+            }
             Token::Variable | Token::Static => {
-                // Check for an identifier         
-                let mut error = false;
-                match token {
-                    Token::Other(variable_id) => {
-                        match variable_id.parse::<i64>() {
-                            Ok(_) => error = true,
-                            Err(_) => {},
-                        }
-                    }
-                    _ => error = true,
-                }
-
-                if error {
-                    self.data().logger.add_log(
-                        Log::new(
-                            LogLevel::Error,
-                            "Invalid identifier for variable".to_string(),
-                            format!(
-                                "{}Found '{}' but it cannot be used as a variable identifier",
-                                line_to_string(line, token_i + 1),
-                                token.to_string()
-                            )
-                        )
-                        .add_cause(cause.clone())
-                        .finish()
-                    );
+                if !self.check_identifier(line, token, "variable", cause.clone()) {
                     *break_line = true;
                     return;
                 }
 
-                // Check for type definition
+                // Check for type definition, the type of a local variable
+                // can be inferred from its value
                 line_iter_for_next_tokens.next();
                 let next = line_iter_for_next_tokens.next();
                 match next {
                     Some(token) => match token {
                         Token::TypeDef => {}
+                        Token::Assign if matches!(previous_token, Token::Variable) => {}
                         _ => {
                             self.data().logger.add_log(
                                 Log::new(
@@ -131,7 +870,7 @@ impl Checker for SyntaxChecker {
                                         token.to_string()
                                     )
                                 )
-                                .add_cause(cause)
+                                .add_cause(cause.clone())
                                 .finish()
                             )
                         }
@@ -147,7 +886,7 @@ impl Checker for SyntaxChecker {
                                     token.to_string()
                                 )
                             )
-                            .add_cause(cause)
+                            .add_cause(cause.clone())
                             .add_hint(format!(
                                 "Specify the variable's type with '{}' + <type>",
                                 &Token::TypeDef.to_string()
@@ -156,14 +895,79 @@ impl Checker for SyntaxChecker {
                     }
                 }
 
+                self.check_value(line, cause);
                 *break_line = true;
             }
 
-            Token::Print => *break_line = true,
+            Token::Print => {
+                self.check_print(line, cause);
+                *break_line = true;
+            }
             Token::Exit => *break_line = true,
 
             // First token of the line
             Token::None => {
+                if source::is_header(line, source::STRUCT) {
+                    self.check_struct(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::ENUM) {
+                    self.check_enum(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::CONST) {
+                    self.check_const(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::MATCH) {
+                    self.check_match(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                let line_i = self.data().line_i;
+                if source::LoopHeader::parse(&self.data().lines[line_i].text).is_some() {
+                    self.check_loop(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::BREAK) || source::is_header(line, source::CONTINUE) {
+                    self.check_jump(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::IF) {
+                    self.check_condition(cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_branch(line) {
+                    self.check_lonely_branch(line, cause);
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::PRINTLN) {
+                    self.check_print(line, cause);
+                    *break_line = true;
+                    return;
+                }
+
+                if let Token::Other(_) = token {
+                    let line_i = self.data().line_i;
+                    if split_assignment(&self.data().lines[line_i].text).is_some() {
+                        self.check_assignment(line, cause);
+                        *break_line = true;
+                        return;
+                    }
+                    if source::is_call(&self.data().lines[line_i]) {
+                        self.check_call(line, cause);
+                        *break_line = true;
+                        return;
+                    }
+                }
+
                 // Lonely token
                 if line.len() == 1 {
                     match token {
@@ -0,0 +1,1111 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use std::collections::HashMap as Dict;
+
+use jup::tokens::Token;
+
+use logging::*;
+use logging::level::LogLevel;
+use logging::log::Log;
+use logging::logger::Logger;
+
+use objects::{
+    enumeration::Enum,
+    expression::{split_assignment, Context, Expression, Literal},
+    format::Format,
+    function::{Function, Signature},
+    range::Range,
+    structure::Struct,
+    type_::Type,
+    variable::Definition,
+};
+
+use crate::base::Checker;
+use crate::data::CheckerData;
+use crate::entry;
+use crate::source;
+use crate::symbols::{Resolution, Symbol, SymbolKind, SymbolTable};
+
+/// Types of the identifiers that can be used at a point of a source file :
+/// the local variables already defined in the function and the static
+/// variables and constants of the project
+pub struct Scope<'a> {
+    pub locals: &'a Dict<String, Type>,
+    pub symbols: &'a SymbolTable,
+    pub module: &'a str,
+}
+
+impl<'a> Context for Scope<'a> {
+    fn type_of(&self, identifier: &str) -> Option<Type> {
+        if let Some(type_) = self.locals.get(identifier) {
+            return Some(type_.clone());
+        }
+
+        match self.symbols.resolve(identifier, self.module) {
+            Resolution::Found(symbol) => match symbol.kind {
+                SymbolKind::Static(variable) => Some(variable.type_().clone()),
+                SymbolKind::Const(constant) => Some(constant.type_),
+                _ => None,
+            }
+            _ => None,
+        }
+    }
+
+    fn struct_(&self, name: &str) -> Option<Struct> {
+        self.symbols.struct_(name, self.module)
+    }
+
+    fn enum_(&self, name: &str) -> Option<Enum> {
+        self.symbols.enum_(name, self.module)
+    }
+
+    fn constant(&self, name: &str) -> Option<i128> {
+        // A local variable hides the constants
+        if self.locals.contains_key(name) {
+            return None;
+        }
+        self.symbols.constant(name, self.module)?.value
+    }
+
+    fn function(&self, name: &str) -> Option<Function> {
+        self.symbols.function(name, self.module)
+    }
+}
+
+/// Check that the values given to the variables match with their types
+pub struct TypesChecker {
+    data: CheckerData,
+    /// Local variables of the function being checked
+    locals: Dict<String, Type>,
+    /// Type returned by the function being checked, `None` when it returns
+    /// nothing
+    return_type: Option<Type>,
+    /// Counters of the loops "for" being checked, from the outermost one :
+    /// the first line after the body, the name of the counter and the type
+    /// of the local variable it hides
+    counters: Vec<(usize, String, Option<Type>)>,
+}
+
+impl TypesChecker {
+    pub fn new(data: CheckerData) -> Self {
+        Self {
+            data,
+            locals: Dict::new(),
+            return_type: None,
+            counters: vec![],
+        }
+    }
+
+    fn module(&mut self) -> String {
+        let source = self.data().source.clone();
+        match self.data().project.sources.iter().find(| x | x.path == source) {
+            Some(source_file) => source_file.module(),
+            None => String::new(),
+        }
+    }
+
+    /// Check the variable defined by the line, a static variable is in the
+    /// data section so its value has to be a literal
+    fn check_definition(&mut self, line: &Vec<Token>, is_static: bool) {
+        let line_i = self.data().line_i;
+        let definition = Definition::parse(self.data().lines[line_i].rest());
+
+        let inferred = {
+            let module = self.module();
+            let project = self.data().project.clone();
+            let scope = Scope {
+                locals: &self.locals,
+                symbols: &project.symbols,
+                module: &module,
+            };
+            definition.infer_type(&scope)
+        };
+
+        let type_ = match inferred {
+            Ok(type_) => type_,
+            Err(reason) => {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Unknown type".to_string(),
+                        format!(
+                            "{}The type of '{}' cannot be determined from its value",
+                            line_to_string(line, 0),
+                            definition.id
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!(
+                        "{}, specify the variable's type with '{}' + <type>",
+                        reason,
+                        Token::TypeDef.to_string()
+                    ))
+                );
+                return;
+            }
+        };
+
+        if let Some(name) = self.unknown_type(&type_) {
+            let reason = self.unknown_reason(&name);
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Unknown type".to_string(),
+                    format!(
+                        "{}The type of '{}' is not known",
+                        line_to_string(line, 0),
+                        definition.id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+            return;
+        }
+
+        if has_str_elements(&type_) {
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid type".to_string(),
+                    format!(
+                        "{}The elements of '{}' cannot be strings",
+                        line_to_string(line, 0),
+                        definition.id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(format!("A string is given by a pointer to it, '*{}'", Type::Str))
+            );
+            return;
+        }
+
+        // The variable is stored with the characters of its value
+        let is_text = definition.value.as_ref()
+            .map(| x | Expression::parse(x))
+            .map_or(false, | x | matches!(x, Ok(Expression::Literal(Literal::Str(_)))));
+
+        if !is_static && type_ == Type::Str && !is_text {
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid value".to_string(),
+                    format!(
+                        "{}The string '{}' is not given a text",
+                        line_to_string(line, 0),
+                        definition.id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint("A local string is initialized by a string literal".to_string())
+            );
+        } else if let Some(ref value) = definition.value {
+            let expression = match Expression::parse(value) {
+                Ok(expression) => expression,
+                Err(_) => return, // reported by the syntax checker
+            };
+
+            let module = self.module();
+            let project = self.data().project.clone();
+            let scope = Scope {
+                locals: &self.locals,
+                symbols: &project.symbols,
+                module: &module,
+            };
+
+            let result = expression.expect_type(&type_, &scope).and_then(| () | {
+                if !is_static || expression.is_constant(&type_, &scope) {
+                    return Ok(());
+                }
+                match expression.constant_value_as(Some(&type_), &scope) {
+                    Err(reason) if type_.is_integer() => Err(reason),
+                    _ => Err(format!(
+                        "The static variable '{}' must be initialized by a value known \
+                        at compile time",
+                        definition.id
+                    )),
+                }
+            });
+
+            if let Err(reason) = result {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid value".to_string(),
+                        format!(
+                            "{}The value of '{}' cannot be used as a '{}'",
+                            line_to_string(line, 0),
+                            definition.id,
+                            type_
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+            }
+        }
+
+        if !is_static {
+            self.locals.insert(definition.id, type_);
+        }
+    }
+
+    /// Check the types of the parameters of the function declared by the
+    /// line, they are the first local variables of the function, and its
+    /// return type
+    fn check_function(&mut self, line: &Vec<Token>) {
+        self.locals.clear();
+        self.counters.clear();
+        self.return_type = None;
+
+        let line_i = self.data().line_i;
+        let signature = match Signature::parse(self.data().lines[line_i].rest()) {
+            Ok(signature) => signature,
+            Err(_) => return, // reported by the syntax checker
+        };
+
+        let return_type = signature.return_type.as_ref()
+            .map(| x | self.resolve(x));
+        self.return_type = entry::return_type(&signature.id, return_type);
+
+        if let Some(type_) = self.return_type.clone() {
+            let reason = match self.unknown_type(&type_) {
+                Some(name) => Some(self.unknown_reason(&name)),
+                None if matches!(type_, Type::Array(_, _) | Type::Str) => Some(format!(
+                    "A function cannot return a '{}', return a pointer to it",
+                    type_
+                )),
+                None => None,
+            };
+
+            if let Some(reason) = reason {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid return type".to_string(),
+                        format!(
+                            "{}The function '{}' cannot return a value of type '{}'",
+                            line_to_string(line, 0),
+                            signature.id,
+                            type_
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+                self.return_type = None;
+            }
+        }
+
+        for param in signature.params {
+            let type_ = self.resolve(&param.type_.unwrap_or_default());
+
+            if let Some(name) = self.unknown_type(&type_) {
+                let reason = self.unknown_reason(&name);
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Unknown type".to_string(),
+                        format!(
+                            "{}The type of the parameter '{}' is not known",
+                            line_to_string(line, 0),
+                            param.id
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+                continue;
+            }
+
+            // A string has the size of its text, only a copy of its first
+            // character would be passed
+            if type_ == Type::Str || has_str_elements(&type_) {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid parameter".to_string(),
+                        format!(
+                            "{}The parameter '{}' cannot be a '{}'",
+                            line_to_string(line, 0),
+                            param.id,
+                            type_
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!("A string is given by a pointer to it, '*{}'", Type::Str))
+                );
+            }
+            self.locals.insert(param.id, type_);
+        }
+    }
+
+    /// Check the value returned by the line against the return type of the
+    /// function
+    fn check_return(&mut self, line: &Vec<Token>) {
+        let line_i = self.data().line_i;
+        let value = self.data().lines[line_i].rest().to_string();
+        let cause = source_to_string(self.data().source.clone(), line_i, 0);
+
+        let type_ = match (self.return_type.clone(), value.is_empty()) {
+            (Some(type_), false) => type_,
+            (None, true) => return,
+            (Some(type_), true) => {
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Missing return value".to_string(),
+                        format!(
+                            "{}The function returns a value of type '{}' but no \
+                            value is given",
+                            line_to_string(line, 0),
+                            type_
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!(
+                        "Return a value with '{} <value>'",
+                        Token::Return.to_string()
+                    ))
+                );
+                return;
+            }
+            (None, false) => {
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Unexpected return value".to_string(),
+                        format!(
+                            "{}The function returns nothing but the value '{}' is given",
+                            line_to_string(line, 0),
+                            value
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!(
+                        "Declare the returned type with 'func <id>(...){} <type>'",
+                        Token::TypeDef.to_string()
+                    ))
+                );
+                return;
+            }
+        };
+
+        let expression = match Expression::parse(&value) {
+            Ok(expression) => expression,
+            Err(_) => return, // reported by the syntax checker
+        };
+
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+
+        if let Err(reason) = expression.expect_type(&type_, &scope) {
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid return value".to_string(),
+                    format!(
+                        "{}The value '{}' cannot be returned as a '{}'",
+                        line_to_string(line, 0),
+                        value,
+                        type_
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Type written in the source code, the structures are replaced by their
+    /// declarations
+    fn resolve(&mut self, type_as_string: &str) -> Type {
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+        Type::from_string(type_as_string.to_string()).resolve(&scope)
+    }
+
+    /// Name of the type that is not known in a resolved type, the pointed
+    /// types included
+    fn unknown_type(&mut self, type_: &Type) -> Option<String> {
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+        type_.innermost().resolve(&scope).unknown().cloned()
+    }
+
+    /// Why the type named `name` is not known
+    fn unknown_reason(&mut self, name: &str) -> String {
+        let module = self.module();
+        match self.data().project.symbols.resolve(name, &module) {
+            Resolution::Found(Symbol { kind: SymbolKind::Struct(None), .. }) => format!(
+                "The layout of '{}' is not known, a field has an unknown type or \
+                contains the structure itself",
+                name
+            ),
+            Resolution::Ambiguous(_) => format!("Many types are named '{}'", name),
+            _ => format!("No structure or enum is named '{}'", name),
+        }
+    }
+
+    /// Check that the fields of the structure declared by the line have known
+    /// types, a structure cannot contain itself
+    fn check_struct(&mut self) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        let name = match parsed[line_i].get(1) {
+            Some(Token::Other(name)) => name.clone(),
+            _ => return, // reported by the syntax checker
+        };
+
+        for field_i in source::block_statements(&parsed, &lines, line_i) {
+            let definition = Definition::parse(&lines[field_i].text);
+            let type_ = match definition.type_ {
+                Some(ref type_) => self.resolve(type_),
+                None => continue, // reported by the syntax checker
+            };
+
+            // A string has the size of its text, a field has a fixed size
+            if type_ == Type::Str || has_str_elements(&type_) {
+                let cause = source_to_string(self.data().source.clone(), field_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid type".to_string(),
+                        format!(
+                            "{}The field '{}' cannot be a '{}'",
+                            line_to_string(&parsed[field_i], 0),
+                            definition.id,
+                            type_
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!("A string is given by a pointer to it, '*{}'", Type::Str))
+                );
+                continue;
+            }
+
+            let unknown = match self.unknown_type(&type_) {
+                Some(unknown) => unknown,
+                None => continue,
+            };
+
+            let reason = match unknown == name {
+                true => format!("The structure '{}' cannot contain itself", name),
+                false => self.unknown_reason(&unknown),
+            };
+
+            let cause = source_to_string(self.data().source.clone(), field_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Unknown type".to_string(),
+                    format!(
+                        "{}The type of the field '{}' is not known",
+                        line_to_string(&parsed[field_i], 0),
+                        definition.id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check that the constant declared by the line has an integer, boolean or
+    /// enum type and that its value can be computed, SEE `Constant::compute()`
+    fn check_const(&mut self, line: &Vec<Token>) {
+        let source = self.data().source.clone();
+        let line_i = self.data().line_i;
+        let project = self.data().project.clone();
+
+        let symbol = match project.symbols.symbols()
+            .iter()
+            .find(| x | x.source == source && x.line_i == line_i)
+        {
+            Some(symbol) => symbol.clone(),
+            None => return, // reported by the syntax checker
+        };
+        let constant = match symbol.kind {
+            SymbolKind::Const(ref constant) => constant.clone(),
+            _ => return,
+        };
+
+        let type_ = &constant.type_;
+        let reason = if let Some(name) = self.unknown_type(type_) {
+            Some(self.unknown_reason(&name))
+        } else if !type_.is_integer() && !type_.is_enum() && *type_ != Type::Bool {
+            Some(format!(
+                "A constant is an integer, a boolean or an enum, not a value of type '{}'",
+                type_
+            ))
+        } else if constant.value.is_none() {
+            match const_cycle(&project.symbols, &mut vec![symbol.clone()]) {
+                Some(cycle) => Some(format!(
+                    "The value of '{}' depends on itself : {}",
+                    constant.id,
+                    cycle.join(" -> ")
+                )),
+                // The local variables of the functions are not visible
+                None => constant.compute(&Scope {
+                    locals: &Dict::new(),
+                    symbols: &project.symbols,
+                    module: &symbol.module,
+                }).err(),
+            }
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            let cause = source_to_string(source, line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid constant".to_string(),
+                    format!(
+                        "{}The value of '{}' cannot be computed at compile time",
+                        line_to_string(line, 2),
+                        constant.id
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check that the patterns of the match statement started by the line
+    /// are values of the matched type, given once, and that all the values
+    /// are matched : every variant of an enum, "true" and "false", or any
+    /// value with the arm "else"
+    fn check_match(&mut self, line: &Vec<Token>) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        let subject = match Expression::parse(lines[line_i].rest()) {
+            Ok(subject) => subject,
+            Err(_) => return, // reported by the syntax checker
+        };
+
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+
+        let type_ = match subject.type_(&scope) {
+            Ok(type_) if type_.is_integer() || type_.is_enum() || type_ == Type::Bool => type_,
+            result => {
+                let reason = match result {
+                    Ok(type_) => format!(
+                        "Only integers, booleans and enums are matched, '{}' gives '{}'",
+                        subject,
+                        type_
+                    ),
+                    Err(reason) => reason,
+                };
+
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid match".to_string(),
+                        format!(
+                            "{}The value '{}' cannot be matched",
+                            line_to_string(line, 2),
+                            subject
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+                return;
+            }
+        };
+
+        let mut matched: Vec<i128> = vec![];
+        let mut has_else = false;
+        let mut errors: Vec<(usize, String)> = vec![];
+
+        for arm_i in source::block_statements(&parsed, &lines, line_i) {
+            let patterns = match source::arm_patterns(&lines[arm_i].text) {
+                Ok(Some(patterns)) => patterns,
+                Ok(None) => {
+                    has_else = true;
+                    continue;
+                }
+                Err(_) => continue, // reported by the syntax checker
+            };
+
+            for pattern in patterns {
+                let value = pattern.constant_value(&scope).ok();
+                let reason = match (value, pattern.expect_type(&type_, &scope)) {
+                    (None, _) => Some(format!(
+                        "'{}' is not a literal, a constant or a variant of an enum",
+                        pattern
+                    )),
+                    (_, Err(reason)) => Some(reason),
+                    (Some(value), _) if matched.contains(&value) => Some(format!(
+                        "'{}' is already matched by a previous arm",
+                        pattern
+                    )),
+                    (Some(value), _) => {
+                        matched.push(value);
+                        None
+                    }
+                };
+
+                if let Some(reason) = reason {
+                    errors.push((arm_i, reason));
+                }
+            }
+        }
+
+        let missing: Option<Vec<String>> = match type_ {
+            _ if has_else => None,
+            Type::Enum(ref enum_) => Some(
+                enum_.variants.iter()
+                    .filter(| (_, value) | !matched.contains(value))
+                    .map(| (variant, _) | format!("'{}.{}'", enum_.name, variant))
+                    .collect()
+            ),
+            Type::Bool => Some(
+                [false, true].iter()
+                    .filter(| x | !matched.contains(&(**x as i128)))
+                    .map(| x | format!("'{}'", x))
+                    .collect()
+            ),
+            // Too many values to be all written
+            _ => Some(vec![format!("the other values of '{}'", type_)]),
+        };
+
+        for (arm_i, reason) in errors {
+            let cause = source_to_string(self.data().source.clone(), arm_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid pattern".to_string(),
+                    format!(
+                        "{}A pattern of this arm cannot match a value of type '{}'",
+                        line_to_string(&parsed[arm_i], 0),
+                        type_
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+
+        match missing {
+            Some(missing) if !missing.is_empty() => {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Non-exhaustive match".to_string(),
+                        format!(
+                            "{}Not all the values of '{}' are matched, missing {}",
+                            line_to_string(line, 2),
+                            subject,
+                            missing.join(", ")
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(format!(
+                        "Add the arms of the missing values or an arm '{}'",
+                        source::ELSE
+                    ))
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Check that the condition of the branch "if" or "elif" or of the loop
+    /// "while" started by the line is a boolean, like a comparison
+    fn check_condition(&mut self, line: &Vec<Token>, condition: &str) {
+        let line_i = self.data().line_i;
+        let condition = match Expression::parse(condition) {
+            Ok(condition) => condition,
+            Err(_) => return, // reported by the syntax checker
+        };
+
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+
+        if let Err(reason) = condition.expect_type(&Type::Bool, &scope) {
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid condition".to_string(),
+                    format!(
+                        "{}The condition '{}' is not a boolean",
+                        line_to_string(line, 2),
+                        condition
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check that the range of the loop "for" started by the line counts
+    /// integers with a constant step, its counter is a local variable of
+    /// the body
+    fn check_range(&mut self, line: &Vec<Token>, text: &str) {
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+        let line_i = self.data().line_i;
+
+        let range = match Range::parse(text) {
+            Ok(range) => range,
+            Err(_) => return, // reported by the syntax checker
+        };
+
+        let result = {
+            let module = self.module();
+            let project = self.data().project.clone();
+            let scope = Scope {
+                locals: &self.locals,
+                symbols: &project.symbols,
+                module: &module,
+            };
+            range.type_(&scope)
+                .and_then(| type_ | range.step_value(&type_, &scope).map(| _ | type_))
+        };
+
+        match result {
+            Ok(type_) => {
+                let hidden = self.locals.insert(range.counter.clone(), type_);
+                let end = source::block_end(&parsed, &lines, line_i);
+                self.counters.push((end, range.counter, hidden));
+            }
+            Err(reason) => {
+                let cause = source_to_string(self.data().source.clone(), line_i, 0);
+                self.data().logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Invalid range".to_string(),
+                        format!(
+                            "{}The counter '{}' cannot take the values of this range",
+                            line_to_string(line, 2),
+                            range.counter
+                        )
+                    )
+                    .add_cause(cause)
+                    .add_hint(reason)
+                );
+            }
+        }
+    }
+
+    /// Remove the counters of the loops "for" whose body is ended at the
+    /// current line, the hidden local variables are visible again
+    fn close_counters(&mut self) {
+        let line_i = self.data().line_i;
+
+        while let Some((end, counter, hidden)) = self.counters.last().cloned() {
+            if line_i < end {
+                break;
+            }
+            self.counters.pop();
+
+            match hidden {
+                Some(type_) => self.locals.insert(counter, type_),
+                None => self.locals.remove(&counter),
+            };
+        }
+    }
+
+    /// Check the arguments of the function called by the line against its
+    /// parameters, its returned value is not used
+    fn check_call(&mut self, line: &Vec<Token>) {
+        let line_i = self.data().line_i;
+        let (name, args) = match Expression::parse(&self.data().lines[line_i].text) {
+            Ok(Expression::Call(name, args)) => (name, args),
+            _ => return, // reported by the syntax checker
+        };
+
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+
+        if let Err(reason) = Expression::callee(&name, &args, &scope) {
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid call".to_string(),
+                    format!(
+                        "{}The function '{}' cannot be called with these arguments",
+                        line_to_string(line, 0),
+                        name
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check that each value of the printed message can be written
+    fn check_print(&mut self, line: &Vec<Token>) {
+        let line_i = self.data().line_i;
+        let format = match Format::parse(self.data().lines[line_i].rest()) {
+            Ok(format) => format,
+            Err(_) => return, // reported by the syntax checker
+        };
+
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+
+        if let Err(reason) = format.check(&scope) {
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid print".to_string(),
+                    format!(
+                        "{}The printed message cannot be written",
+                        line_to_string(line, 1)
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+
+    /// Check that the assigned value has the type of its target
+    fn check_assignment(&mut self, line: &Vec<Token>) {
+        let line_i = self.data().line_i;
+        let text = self.data().lines[line_i].text.clone();
+
+        let (target, value) = match split_assignment(&text) {
+            Some((target, value)) => match (Expression::parse(target), Expression::parse(value)) {
+                (Ok(target), Ok(value)) => (target, value),
+                _ => return, // reported by the syntax checker
+            }
+            None => return,
+        };
+
+        let module = self.module();
+        let project = self.data().project.clone();
+        let scope = Scope {
+            locals: &self.locals,
+            symbols: &project.symbols,
+            module: &module,
+        };
+
+        let result = match target.is_constant_name(&scope) {
+            true => Err(format!("'{}' is a constant, its value cannot change", target)),
+            false => target.type_(&scope).and_then(| type_ | match type_ {
+                // Its size is the one of its first text
+                Type::Str => Err(format!("The text of '{}' cannot change", target)),
+                _ => value.expect_type(&type_, &scope),
+            }),
+        };
+
+        if let Err(reason) = result {
+            let cause = source_to_string(self.data().source.clone(), line_i, 0);
+            self.data().logger.add_log(
+                Log::new(
+                    LogLevel::Error,
+                    "Invalid value".to_string(),
+                    format!(
+                        "{}The value '{}' cannot be assigned to '{}'",
+                        line_to_string(line, 0),
+                        value,
+                        target
+                    )
+                )
+                .add_cause(cause)
+                .add_hint(reason)
+            );
+        }
+    }
+}
+
+/// If `type_` is an array of strings, at any depth, the size of a string
+/// is only known from its text
+fn has_str_elements(type_: &Type) -> bool {
+    match type_ {
+        Type::Array(element, _) => **element == Type::Str || has_str_elements(element),
+        _ => false,
+    }
+}
+
+/// Scoped names of the constants from the last one of `path` back to the
+/// first one, when the value of the first one depends on itself
+fn const_cycle(symbols: &SymbolTable, path: &mut Vec<Symbol>) -> Option<Vec<String>> {
+    let current = path.last()?.clone();
+    let expression = match current.kind {
+        SymbolKind::Const(ref constant) => Expression::parse(&constant.expression).ok()?,
+        _ => return None,
+    };
+
+    for name in expression.identifiers() {
+        // Only the constants that are not computed can be part of a cycle
+        let next = match symbols.resolve(&name, &current.module) {
+            Resolution::Found(symbol) => match symbol.kind {
+                SymbolKind::Const(ref constant) if constant.value.is_none() => symbol.clone(),
+                _ => continue,
+            }
+            _ => continue,
+        };
+
+        if next.id == path[0].id {
+            let mut cycle: Vec<String> = path.iter().map(| x | x.id.clone()).collect();
+            cycle.push(next.id);
+            return Some(cycle);
+        }
+        if path.iter().any(| x | x.id == next.id) {
+            continue;
+        }
+
+        path.push(next);
+        if let Some(cycle) = const_cycle(symbols, path) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+    None
+}
+
+/// SEE Functions' documentation from `Checker` because they are not
+/// written here a new time
+impl Checker for TypesChecker {
+    fn name(&self) -> &'static str {
+        "types"
+    }
+
+    fn is_required(&self) -> bool {
+        true
+    }
+
+    fn init(&mut self) {
+
+    }
+
+    /// Only the first token of each line is checked, the values are parsed
+    /// from the source code
+    fn check(&mut self) {
+        let parsed = self.data().parsed.clone();
+
+        for line in parsed.iter() {
+            let mut previous_token = Token::None;
+            let mut break_line = false;
+
+            self.data().token_i = 0;
+            self.close_counters();
+            if let Some(token) = line.first() {
+                self.check_for_instruction(
+                    line,
+                    &mut break_line,
+                    token,
+                    &mut previous_token,
+                );
+            }
+            self.data().line_i += 1;
+        }
+    }
+
+    fn check_for_instruction(
+        &mut self,
+        line: &Vec<Token>,
+        break_line: &mut bool,
+        token: &Token,
+        _previous_token: &mut Token
+    ) {
+        let line_i = self.data().line_i;
+        let loop_header = source::LoopHeader::parse(&self.data().lines[line_i].text);
+
+        match token {
+            Token::Function => self.check_function(line),
+            Token::Return => self.check_return(line),
+            Token::Variable => self.check_definition(line, false),
+            Token::Static => self.check_definition(line, true),
+            Token::Print => self.check_print(line),
+            Token::Other(_) if source::is_header(line, source::PRINTLN) => self.check_print(line),
+            Token::Other(_) if source::is_header(line, source::STRUCT) => self.check_struct(),
+            Token::Other(_) if source::is_header(line, source::ENUM) => {}, // SEE "syntax"
+            Token::Other(_) if source::is_header(line, source::CONST) => self.check_const(line),
+            Token::Other(_) if source::is_header(line, source::MATCH) => self.check_match(line),
+            Token::Other(_) if source::is_branch(line) => {
+                let line_i = self.data().line_i;
+                let condition = self.data().lines[line_i].rest().to_string();
+                if !condition.is_empty() {
+                    self.check_condition(line, &condition);
+                }
+            }
+            _ if loop_header.is_some() => {
+                let header = loop_header.unwrap();
+                match header.keyword.as_str() {
+                    source::WHILE => self.check_condition(line, &header.condition),
+                    source::FOR => self.check_range(line, &header.condition),
+                    _ => {}
+                }
+            }
+            Token::Other(_) if source::is_call(&self.data().lines[line_i]) => {
+                self.check_call(line);
+            }
+            Token::Other(_) => self.check_assignment(line),
+            _ => {}
+        }
+        *break_line = true;
+    }
+
+    fn finish(&mut self) -> Result<(), Logger> {
+        self.data().logger.get_result()
+    }
+
+    fn data(&mut self) -> &mut CheckerData {
+        &mut self.data
+    }
+}
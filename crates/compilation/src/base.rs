@@ -6,6 +6,7 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::process;
+use std::rc::Rc;
 
 use jup::{
     parser::Parser, 
@@ -13,13 +14,19 @@ use jup::{
 };
 
 use checking;
-use checking::data::CheckerData;
+use checking::{
+    data::{CheckerData, ProjectData, SourceFile},
+    source,
+    symbols::{Resolution, SymbolKind, SymbolTable},
+};
 
 use crate::{
+    block::{Block, BlockKind, Counter},
     caller::Caller,
     data::CompilerData,
     defaults,
     scope::Scope,
+    symbols,
 };
 
 use logging::level::LogLevel;
@@ -27,7 +34,11 @@ use logging::log::Log;
 use logging::logger::Logger;
 
 use objects::{
+    expression::{split_assignment, Expression},
+    format::Format,
     function::Function, 
+    structure::align,
+    type_::Type,
     variable::Variable
 };
 
@@ -41,6 +52,21 @@ pub trait Compiler: Caller {
     /// Do some stuff useful
     fn init(&mut self);
 
+    /// Parse a source file \
+    /// All the source files are parsed before being checked, some checkers
+    /// need to know the whole project
+    fn parse_one(&mut self, source: &String) -> SourceFile {
+        let mut parser = Parser::from_path(Path::new(source)).unwrap();
+        parser.run();
+
+        let parsed = parser.parsed().clone();
+        SourceFile {
+            path: source.clone(),
+            lines: checking::source::lines(source, &parsed),
+            parsed,
+        }
+    }
+
     /// Starting point for each source file
     fn init_one(&mut self, source: &String) -> Result<(), Logger> {
         self.data().current_source = format!(
@@ -48,30 +74,38 @@ pub trait Compiler: Caller {
             defaults::BUILD_FOLDER, 
             source
         );
-        
-        self.data().parser = Some(
-            Parser::from_path(Path::new(source)).unwrap()
-        );
 
-        self.data().parser.as_mut()
-            .unwrap()
-            .run();
+        let project = self.data().project.clone();
+        let source_file = project.sources.iter()
+            .find(| x | x.path == *source)
+            .unwrap(); // all sources are parsed
+
+        self.data().parsed = source_file.parsed.clone();
+        self.data().lines = source_file.lines.clone();
 
         // run all checkers for the current source file
-        let checker_data = CheckerData {
-            source: source.clone(),
-            parsed: self.data().parser.as_ref().unwrap().parsed().clone(),
-            logger: Logger::new(),
-            line_i: 0,
-            token_i: 0,
-        };
-        checking::run_checkers(checker_data)
+        let selection = self.data().checker_selection.clone();
+        checking::run_checkers(
+            CheckerData::new(source_file, project.clone()),
+            &selection
+        )
     }
 
     /// Main function where each source file is transformed to an objet file
     fn run(&mut self) {
         self.init();
 
+        let mut project = ProjectData {
+            is_library: self.data().is_library,
+            sources: vec![],
+            symbols: SymbolTable::new(),
+        };
+        for source in self.data().sources.clone() {
+            project.sources.push(self.parse_one(&source));
+        }
+        project.symbols = symbols::build_table(&project);
+        self.data().project = Rc::new(project);
+
         // Returned logs
         let mut loggers: Vec<Logger> = vec!();
 
@@ -117,13 +151,31 @@ pub trait Compiler: Caller {
 
     /// Methods caller according to the current token
     fn call(&mut self) {
-        let parsed: Vec<Vec<Token>> = self.data().parser.as_ref()
-            .unwrap()
-            .parsed()
-            .to_vec();
+        let parsed: Vec<Vec<Token>> = self.data().parsed.clone();
+        let mut struct_end = 0;
+
+        for (line_i, line) in parsed.iter().enumerate() {
+            if line.is_empty() || line_i < struct_end {
+                continue;
+            }
+
+            // The line is not part of the blocks less or equally indented
+            let indent = self.data().lines[line_i].indent;
+            self.close_blocks(Some(indent));
+
+            // A structure, enum or constant declaration gives no code, SEE
+            // `symbols::build_table()`
+            if source::is_header(line, source::STRUCT) {
+                let lines = self.data().lines.clone();
+                struct_end = source::block_end(&parsed, &lines, line_i);
+                continue;
+            }
+            if source::is_header(line, source::ENUM) || source::is_header(line, source::CONST) {
+                continue;
+            }
 
-        for line in parsed.iter() {
             self.data().current_line = line.clone();
+            self.data().current_line_i = line_i;
 
             let mut previous_token = Token::None;
             let mut break_line = false; // to break the loop from the closure
@@ -143,6 +195,23 @@ pub trait Compiler: Caller {
                 previous_token = token.clone();
             }
         }
+
+        self.close_blocks(None);
+    }
+
+    /// Close the opened blocks, from the innermost, that a line with the
+    /// given indentation is not part of \
+    /// All blocks are closed when there is no indentation (end of file)
+    fn close_blocks(&mut self, indent: Option<usize>) {
+        while let Some(block) = self.data().blocks.last().cloned() {
+            match indent {
+                Some(indent) if indent > block.indent => break,
+                _ => {}
+            }
+
+            self.data().blocks.pop();
+            self.when_block_end(block);
+        }
     }
 
     /// Function "linked" with `call()` because it does the `Caller` calls
@@ -167,7 +236,7 @@ pub trait Compiler: Caller {
                 *break_line = true;
                 return;
             }
-            Token::Assign => self.when_assign(line.to_vec()),
+            Token::Assign => {}, // SEE the first token of the line
             Token::Function => self.when_function(next_tokens),
             Token::Return => self.when_return(next_tokens),
             Token::Static => {
@@ -194,6 +263,60 @@ pub trait Compiler: Caller {
 
             // First token of the line
             Token::None => {
+                // A line directly in a match is an arm, the lines of its body
+                // are in the block of the arm
+                let is_arm = matches!(
+                    self.data().blocks.last(),
+                    Some(Block { kind: BlockKind::Match { .. }, .. })
+                );
+                if is_arm {
+                    self.when_match_arm();
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::MATCH) {
+                    self.when_match();
+                    *break_line = true;
+                    return;
+                }
+                if source::is_branch(line) {
+                    self.when_branch();
+                    *break_line = true;
+                    return;
+                }
+                let line_i = self.data().current_line_i;
+                if source::LoopHeader::parse(&self.data().lines[line_i].text).is_some() {
+                    self.when_loop();
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::BREAK) || source::is_header(line, source::CONTINUE) {
+                    self.when_jump();
+                    *break_line = true;
+                    return;
+                }
+                if source::is_header(line, source::PRINTLN) {
+                    self.when_print(vec![]);
+                    *break_line = true;
+                    return;
+                }
+
+                // The assignments are detected from the source code because
+                // the tokens of their target and their value are not known
+                if let Token::Other(_) = token {
+                    let line_i = self.data().current_line_i;
+                    if split_assignment(&self.data().lines[line_i].text).is_some() {
+                        self.when_assign(line.to_vec());
+                        *break_line = true;
+                        return;
+                    }
+                    if source::is_call(&self.data().lines[line_i]) {
+                        self.when_call();
+                        *break_line = true;
+                        return;
+                    }
+                }
+
                 // Lonely token, execute it right now
                 if line.len() == 1 {
                     *previous_token = token.clone();
@@ -211,6 +334,55 @@ pub trait Compiler: Caller {
         }
     }
 
+    /// Declare a symbol of another object file, once per object file
+    fn use_extern(&mut self, id: String) {
+        if !self.data().externs.contains(&id) {
+            self.data().externs.push(id.clone());
+            self.add_assembly_code(format!("extern {}", id));
+        }
+    }
+
+    /// Function called by the name `name` from the current module, declared
+    /// when it comes from another object file
+    fn callee(&mut self, name: &str) -> Function {
+        let module = self.data().current_scope.module();
+        let project = self.data().project.clone();
+
+        match project.symbols.resolve(name, &module) {
+            Resolution::Found(symbol) => {
+                if symbol.module != module {
+                    self.use_extern(symbol.id.clone());
+                }
+                match symbol.kind {
+                    SymbolKind::Function(function) => function,
+                    _ => panic!(), // never happens, checked by "types"
+                }
+            }
+            _ => panic!(), // never happens, checked by "types"
+        }
+    }
+
+    /// Slot of `size` bytes in the stack frame for a local variable, its
+    /// position under "rbp" is a multiple of `alignment` \
+    /// The prologue of the function reserves the whole stack frame, once the
+    /// slots of all its variables are known
+    fn allocate(&mut self, size: usize, alignment: usize) -> usize {
+        let end = self.data().i_variable_stack + size;
+        self.data().i_variable_stack = align(end, alignment);
+        self.data().i_variable_stack
+    }
+
+    /// New label for a jump in the current scope, unique in the object file
+    fn new_label(&mut self, name: &str) -> String {
+        self.data().i_label += 1;
+        format!(
+            "{}.{}_{}",
+            self.data().current_scope.to_string(),
+            name,
+            self.data().i_label
+        )
+    }
+
     /// Link all generated files to one output file (library or binary according
     /// to the selected one)
     fn link(&mut self);
@@ -227,15 +399,56 @@ pub trait Compiler: Caller {
 
     // --- ASM code generators
 
-    /// Variable declaration, can 
+    /// Local variable declaration, in the slot given by its position \
+    /// SEE `Compiler::allocate()`
     fn add_variable(&mut self, variable: Variable);
-    fn add_static_variable(&mut self, variable: Variable);
+    /// Static variable of the object file, its value is not defined when
+    /// `is_initialized` is false, so it's filled by zeros
+    fn add_static_variable(&mut self, variable: Variable, is_initialized: bool);
+    /// Function definition, its parameters are copied from the registers
+    /// and the stack of the calling convention to its local variables
     fn add_function(&mut self, function: Function);
+    /// Call of `function` with the values given by `args`, passed according
+    /// to the calling convention \
+    /// The stack is aligned on 16 bytes at the call, under the values pushed
+    /// by the current statement
+    fn call_function(&mut self, function: &Function, args: &Vec<Expression>);
 
     fn change_variable_value(&mut self, variable: &Variable);
 
-    fn return_(&mut self, value: String);
-
-    fn print(&mut self, to_print: String);
+    /// Store the value given by `value` in the variable or the element given
+    /// by `target`
+    fn assign(&mut self, target: &Expression, value: &Expression);
+
+    /// Place the value given by the expression, of type `type_`, in the
+    /// return register : "rax", or "xmm0" for the floats
+    fn evaluate(&mut self, expression: &Expression, type_: &Type);
+
+    /// Leave the function, the value is returned in "rax" or in "xmm0" for a
+    /// float, and a structure like an argument of the System V AMD64 calling
+    /// convention, SEE `Self::call_function()` \
+    /// A function returning a value gives zero when there is no value
+    fn return_(&mut self, value: Option<&Expression>, type_: &Option<Type>);
+
+    /// Jump to `label` when the value in the evaluation register is `value`
+    fn jump_if_equal(&mut self, value: i128, label: &String);
+    /// Jump to `label` when the boolean given by `condition` is false
+    fn jump_if_false(&mut self, condition: &Expression, label: &String);
+    /// Jump to `label` when the counter of a loop "for" starts out of its
+    /// range
+    fn jump_if_out_of_range(&mut self, counter: &Counter, label: &String);
+    /// Give its next value to the counter of a loop "for", or jump to `label`
+    /// when the next value is out of the range \
+    /// The distance to the end of the range is compared with the step, so
+    /// the counter never overflows
+    fn step_counter(&mut self, counter: &Counter, label: &String);
+    fn jump(&mut self, label: &String);
+    fn add_label(&mut self, label: &String);
+
+    fn add_assembly_code(&mut self, code: String);
+
+    /// Write each piece of the message to the standard output, the values
+    /// are written by the routines of the object file
+    fn print(&mut self, format: &Format);
     fn exit(&mut self, value: String);
 }
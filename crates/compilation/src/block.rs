@@ -0,0 +1,85 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use objects::{
+    type_::Type,
+    variable::Variable,
+};
+
+/// What opened a block
+#[derive(Clone, Debug)]
+pub enum BlockKind {
+    /// Body of a function, with the type of the values it returns
+    Function {
+        return_type: Option<Type>,
+    },
+    /// Match statement, with the labels of the arms not reached yet in their
+    /// order and the label of its end
+    Match {
+        arms: Vec<String>,
+        end: String,
+    },
+    /// Body of an arm, it jumps to the end of its match
+    Arm {
+        end: String,
+    },
+    /// Body of a loop, with its label written in the source code and the
+    /// labels of its start and its end \
+    /// It jumps back to its start
+    Loop {
+        label: Option<String>,
+        start: String,
+        end: String,
+    },
+    /// Body of a counted loop "for", with its label written in the source
+    /// code, the labels of its start, of the next iteration and of its end \
+    /// The variable hidden by the counter is visible again after the loop
+    For {
+        label: Option<String>,
+        start: String,
+        next: String,
+        end: String,
+        counter: Counter,
+        hidden: Option<Variable>,
+    },
+    /// Body of a branch of a condition, with the label of the next branch
+    /// (none for "else") and the label of the end of the condition \
+    /// When it's not the last branch, it jumps to the end of the condition
+    Branch {
+        next: Option<String>,
+        end: String,
+        is_last: bool,
+    },
+}
+
+/// Counter of a loop "for" and the end of its range, evaluated once, both are
+/// variables of the frame \
+/// The counter goes from the start of the range to its end by adding `step`
+#[derive(Clone, Debug)]
+pub struct Counter {
+    pub variable: Variable,
+    pub end: Variable,
+    pub step: i128,
+    pub is_inclusive: bool,
+}
+
+/// An indentation-delimited block of code currently compiled \
+/// It's closed when a line is not more indented than its header
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub indent: usize,
+    /// If the last statement directly in the block was a return instruction
+    pub returned: bool,
+}
+
+impl Block {
+    pub fn new(kind: BlockKind, indent: usize) -> Self {
+        Self {
+            kind,
+            indent,
+            returned: false,
+        }
+    }
+}
@@ -4,75 +4,107 @@
 
 use jup::tokens::Token;
 
+use checking::{
+    asm::{self, AsmLine},
+    entry,
+    source,
+    symbols::Resolution,
+};
+
 use objects::{
-    function::Function,
+    expression::{split_assignment, string_size, Expression, Literal},
+    format::{Format, Piece},
+    function::Signature,
+    range::Range,
     type_, 
     type_::Type,
-    variable::Variable,
+    variable::{Definition, Variable},
 };
 
-use crate::base;
+use crate::{
+    base,
+    block::{Block, BlockKind, Counter},
+};
 
 /// All implementations for tokens \
 /// SEE `parsing::token`
 pub trait Caller {
-    fn when_assembly_code(&mut self, next_tokens: Vec<Token>) 
+    /// The inline assembly line is written as it is, from the source code
+    /// because the tokens lost the spaces \
+    /// SEE `checking::assembly` for its validation
+    fn when_assembly_code(&mut self, _next_tokens: Vec<Token>) 
     where Self: base::Compiler
     {
-        // TODO : adapt this by parsing an asm line as one token
+        let line_i = self.data().current_line_i;
+        let mut asm_line = AsmLine::parse(&self.data().lines[line_i].text);
+
+        // The referenced symbols of the project are replaced by their scoped
+        // names, and declared when they come from another object file
+        let module = self.data().current_scope.module();
+        let project = self.data().project.clone();
+        let mut externs: Vec<String> = vec![];
 
-        // let first = next_tokens.first().unwrap().clone();
+        for operand in asm_line.operands.iter_mut() {
+            *operand = asm::map_identifiers(operand, | identifier | {
+                if asm::is_register(identifier) {
+                    return None;
+                }
+                match project.symbols.resolve(identifier, &module) {
+                    Resolution::Found(symbol) => {
+                        if symbol.module != module {
+                            externs.push(symbol.id.clone());
+                        }
+                        Some(symbol.id)
+                    }
+                    _ => None,
+                }
+            });
+        }
 
-        // for token in next_tokens {
-        //     next_tokens_as_asm += &token.to_string();
-            
-        //     // Do not put space when it's a function label
-        //     match first {
-        //         Token::Other(ref string) => {
-        //             match string.as_str() {
-        //                 "call" | "jmp" => if first != token { continue; },
-        //                 _ => {}
-        //             }
-        //         },
-        //         _ => {}
-        //     }
+        for extern_ in externs {
+            self.use_extern(extern_);
+        }
 
-        //     next_tokens_as_asm += &" ";
-        // }
+        self.add_assembly_code(asm_line.to_string());
     }
 
-    /// If the previous token is not an known identifier, it raises an error \
-    /// Need the `line` and not "next_tokens" because it uses a token placed
-    /// before the assign token  
-    fn when_assign(&mut self, line: Vec<Token>) 
+    /// The target and the value are parsed from the source code, the target
+    /// is a variable or an element of an array \
+    /// SEE `checking::syntax` and `checking::types` for their validation
+    fn when_assign(&mut self, _line: Vec<Token>) 
     where Self: base::Compiler 
     {
-        let mut reversed_line_iter = line.into_iter().rev();
-        
-        let value: String = match reversed_line_iter.next().unwrap() {
-            Token::Other(value) => value,
-            _ => panic!(), // never happens
-        };
-    
-        reversed_line_iter.next(); // Token::Assign
+        let line_i = self.data().current_line_i;
+        let text = self.data().lines[line_i].text.clone();
+        let (target, value) = split_assignment(&text).unwrap();
 
-        let to_assign = match reversed_line_iter.next().unwrap() {
-            Token::Other(identifier) => identifier,
-            _ => todo!(), // not an identifier
+        self.assign(
+            &Expression::parse(target).unwrap(),
+            &Expression::parse(value).unwrap()
+        );
+    }
+    
+    /// A function called as a statement, its returned value is not used
+    fn when_call(&mut self)
+    where Self: base::Compiler
+    {
+        let line_i = self.data().current_line_i;
+        let (name, args) = match Expression::parse(&self.data().lines[line_i].text) {
+            Ok(Expression::Call(name, args)) => (name, args),
+            _ => panic!(), // never happens, checked by "syntax"
         };
 
-        let mut variable = match self.data().variable_stack.get(&to_assign) {
-            Some(v) => v.clone(),
-            None => panic!(), // invalid identifier
-        };
-        variable.set_current_value(value);
-        self.change_variable_value(&variable);
+        let function = self.callee(&name);
+        self.call_function(&function, &args);
     }
-    
+
     fn when_function(&mut self, _next_tokens: Vec<Token>) 
     where Self: base::Compiler 
     {
-        let id: String = self.data().current_token.to_string();
+        let line_i = self.data().current_line_i;
+        let signature = Signature::parse(self.data().lines[line_i].rest())
+            .unwrap(); // checked by "syntax"
+        let id = signature.id.clone();
  
         self.data().current_scope.push(id.to_string());
         let current_scope_copy = self.data().current_scope.clone();
@@ -82,121 +114,383 @@ pub trait Caller {
             self.data().current_scope.push("main".to_string());
         }
 
-        let function = Function::new(
-            self.data().current_scope.to_string(),
-            // TODO :
-            vec![], // params
-            String::new(), // return type
+        let id = self.data().current_scope.to_string();
+        let mut function = signature.function(id, &*self.data());
+        function.set_return_type(
+            entry::return_type(&signature.id, function.return_type().clone())
         );
 
+        let indent = self.data().lines[line_i].indent;
+        self.data().blocks.push(Block::new(
+            BlockKind::Function { return_type: function.return_type().clone() },
+            indent
+        ));
+
         self.add_function(function);
         self.data().current_scope = current_scope_copy;
     }
 
-    fn when_return(&mut self, next_tokens: Vec<Token>)
+    fn when_return(&mut self, _next_tokens: Vec<Token>)
     where Self: base::Compiler 
     {
-        // Only implemented with "ret <value>" and not for an expression or
-        // multiple values
-        self.return_(match next_tokens.iter().next() {
-            Some(token) => match token {
-                // It could be a number, a `Other` does not mean that it's a 
-                // string object
-                Token::Other(return_value) => return_value.to_string(),
-                _ => panic!(), // never happens
+        let line_i = self.data().current_line_i;
+        let value = match self.data().lines[line_i].rest() {
+            "" => None,
+            value => Some(Expression::parse(value)
+                .unwrap()), // checked by "syntax"
+        };
+
+        let return_type = self.data().blocks.iter()
+            .find_map(| x | match x.kind {
+                BlockKind::Function { ref return_type } => Some(return_type.clone()),
+                _ => None,
+            })
+            .flatten();
+        self.return_(value.as_ref(), &return_type);
+
+        if let Some(block) = self.data().blocks.last_mut() {
+            block.returned = true;
+        }
+    }
+
+    /// Called when a line is not part of the block anymore
+    fn when_block_end(&mut self, block: Block)
+    where Self: base::Compiler
+    {
+        match block.kind {
+            BlockKind::Function { return_type } => {
+                // Implicit return, the checker already warned about it
+                if !block.returned {
+                    self.return_(None, &return_type);
+                }
+                self.data().current_scope.pop();
             }
-            None => String::from("0"), // "null" value
-        });
+            BlockKind::Match { end, .. } => self.add_label(&end),
+            BlockKind::Arm { end } => {
+                if !block.returned {
+                    self.jump(&end);
+                }
+            }
+            BlockKind::Loop { start, end, .. } => {
+                self.jump(&start);
+                self.add_label(&end);
+            }
+            BlockKind::For { start, next, end, counter, hidden, .. } => {
+                self.add_label(&next);
+                self.step_counter(&counter, &end);
+                self.jump(&start);
+                self.add_label(&end);
+
+                let id = counter.variable.id().clone();
+                match hidden {
+                    Some(variable) => self.data().variable_stack.insert(id, variable),
+                    None => self.data().variable_stack.remove(&id),
+                };
+            }
+            BlockKind::Branch { next, end, is_last } => {
+                if !is_last {
+                    if !block.returned {
+                        self.jump(&end);
+                    }
+                    self.data().condition_end = Some(end.clone());
+                }
+                if let Some(next) = next {
+                    self.add_label(&next);
+                }
+                if is_last {
+                    self.add_label(&end);
+                }
+            }
+        }
+    }
+
+    /// The matched value is compared with the patterns of the arms in their
+    /// order, the first arm with an equal pattern is run \
+    /// SEE `checking::types` for the exhaustiveness of the arms
+    fn when_match(&mut self)
+    where Self: base::Compiler
+    {
+        let line_i = self.data().current_line_i;
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+
+        let subject = Expression::parse(lines[line_i].rest())
+            .unwrap(); // checked by "syntax"
+        let type_ = subject.type_(&*self.data())
+            .unwrap(); // checked by "types"
+        self.evaluate(&subject, &type_);
+
+        let end = self.new_label("match_end");
+        let mut arms: Vec<String> = vec![];
+        let mut otherwise: Option<String> = None;
+
+        for arm_i in source::block_statements(&parsed, &lines, line_i) {
+            let label = self.new_label("arm");
+            match source::arm_patterns(&lines[arm_i].text).unwrap() {
+                Some(patterns) => for pattern in patterns {
+                    let value = pattern.constant_value(&*self.data())
+                        .unwrap(); // checked by "types"
+                    self.jump_if_equal(value, &label);
+                }
+                None => otherwise = Some(label.clone()),
+            }
+            arms.push(label);
+        }
+
+        // Only reached with the arm "else", the other values are all matched
+        self.jump(&otherwise.unwrap_or(end.clone()));
+
+        let indent = lines[line_i].indent;
+        self.data().blocks.push(Block::new(BlockKind::Match { arms, end }, indent));
+    }
+
+    /// Start the body of the next arm of the current match
+    fn when_match_arm(&mut self)
+    where Self: base::Compiler
+    {
+        let line_i = self.data().current_line_i;
+        let indent = self.data().lines[line_i].indent;
+
+        let (label, end) = match self.data().blocks.last_mut() {
+            Some(Block { kind: BlockKind::Match { arms, end }, .. }) => {
+                (arms.remove(0), end.clone())
+            }
+            _ => panic!(), // never happens, SEE `Compiler::check_for_instruction()`
+        };
+
+        self.add_label(&label);
+        self.data().blocks.push(Block::new(BlockKind::Arm { end }, indent));
+    }
+
+    /// Start a branch of a condition, its body is skipped when its condition
+    /// is false \
+    /// A branch "elif" or "else" follows the branch closed just before it
+    fn when_branch(&mut self)
+    where Self: base::Compiler
+    {
+        let line_i = self.data().current_line_i;
+        let parsed = self.data().parsed.clone();
+        let lines = self.data().lines.clone();
+
+        let end = match source::is_header(&parsed[line_i], source::IF) {
+            true => self.new_label("if_end"),
+            false => self.data().condition_end.take()
+                .unwrap(), // checked by "syntax"
+        };
+
+        let next = match source::is_header(&parsed[line_i], source::ELSE) {
+            true => None,
+            false => {
+                let condition = Expression::parse(lines[line_i].rest())
+                    .unwrap(); // checked by "syntax"
+                let next = self.new_label("branch");
+                self.jump_if_false(&condition, &next);
+                Some(next)
+            }
+        };
+
+        let is_last = source::next_branch(&parsed, &lines, line_i).is_none();
+        let indent = lines[line_i].indent;
+        self.data().blocks.push(Block::new(BlockKind::Branch { next, end, is_last }, indent));
+    }
+
+    /// Start a loop "while" or "loop", the condition of "while" is checked
+    /// before each iteration \
+    /// SEE `Caller::when_for()` for the loops "for"
+    fn when_loop(&mut self)
+    where Self: base::Compiler
+    {
+        let line_i = self.data().current_line_i;
+        let header = source::LoopHeader::parse(&self.data().lines[line_i].text)
+            .unwrap(); // it's a loop header
+
+        if header.keyword == source::FOR {
+            self.when_for(header);
+            return;
+        }
+
+        let name = header.label.clone().unwrap_or(header.keyword.clone());
+        let start = self.new_label(&name);
+        let end = self.new_label(&format!("{}_end", name));
 
-        self.data().current_scope.pop();
+        self.add_label(&start);
+
+        if header.keyword == source::WHILE {
+            let condition = Expression::parse(&header.condition)
+                .unwrap(); // checked by "syntax"
+            self.jump_if_false(&condition, &end);
+        }
+
+        let indent = self.data().lines[line_i].indent;
+        self.data().blocks.push(Block::new(
+            BlockKind::Loop { label: header.label, start, end },
+            indent
+        ));
     }
 
-    fn when_static(&mut self, next_tokens: Vec<Token>) 
+    /// Start a loop "for", the end of its range is evaluated once and its
+    /// counter is a new local variable, only visible in its body
+    fn when_for(&mut self, header: source::LoopHeader)
+    where Self: base::Compiler
+    {
+        let range = Range::parse(&header.condition)
+            .unwrap(); // checked by "syntax"
+        let type_ = range.type_(&*self.data())
+            .unwrap(); // checked by "types"
+        let step = range.step_value(&type_, &*self.data())
+            .unwrap(); // checked by "types"
+
+        let name = header.label.clone().unwrap_or(header.keyword.clone());
+        let start = self.new_label(&name);
+        let next = self.new_label(&format!("{}_next", name));
+        let end = self.new_label(&format!("{}_end", name));
+
+        // Evaluated before the counter exists, it could have the name of a
+        // variable used by the end
+        let position = self.allocate(type_.to_usize(), type_.alignment());
+        let end_variable = Variable::new(
+            format!("{}.end", range.counter),
+            type_.clone(),
+            range.end.to_string(),
+            position
+        );
+        self.add_variable(end_variable.clone());
+        self.data().variable_stack.remove(end_variable.id()); // not visible
+
+        let hidden = self.data().variable_stack.get(&range.counter).cloned();
+        let position = self.allocate(type_.to_usize(), type_.alignment());
+        let variable = Variable::new(
+            range.counter,
+            type_,
+            range.start.to_string(),
+            position
+        );
+        self.add_variable(variable.clone());
+
+        let counter = Counter {
+            variable,
+            end: end_variable,
+            step,
+            is_inclusive: range.is_inclusive,
+        };
+        self.jump_if_out_of_range(&counter, &end);
+
+        self.add_label(&start);
+
+        let line_i = self.data().current_line_i;
+        let indent = self.data().lines[line_i].indent;
+        self.data().blocks.push(Block::new(
+            BlockKind::For { label: header.label, start, next, end, counter, hidden },
+            indent
+        ));
+    }
+
+    /// Jump to the end of the loop with "break" or to its next iteration with
+    /// "continue", the loop is the innermost one or the one with the label
+    fn when_jump(&mut self)
+    where Self: base::Compiler
+    {
+        let line_i = self.data().current_line_i;
+        let line = self.data().lines[line_i].clone();
+        let label = source::jump_label(&line)
+            .unwrap(); // checked by "syntax"
+
+        let (start, end) = self.data().blocks.iter()
+            .rev()
+            .find_map(| x | match x.kind {
+                BlockKind::Loop { label: ref loop_label, ref start, ref end }
+                    if label.is_none() || *loop_label == label =>
+                {
+                    Some((start.clone(), end.clone()))
+                }
+                // The next iteration starts by stepping the counter
+                BlockKind::For { label: ref loop_label, ref next, ref end, .. }
+                    if label.is_none() || *loop_label == label =>
+                {
+                    Some((next.clone(), end.clone()))
+                }
+                _ => None,
+            })
+            .unwrap(); // checked by "syntax"
+
+        match source::is_header(&self.data().parsed[line_i], source::BREAK) {
+            true => self.jump(&end),
+            false => self.jump(&start),
+        }
+    }
+
+    fn when_static(&mut self, _next_tokens: Vec<Token>) 
     where Self: base::Compiler 
     {
-        let (type_, current_value) 
-            = self.retrieve_variable_info(next_tokens);
+        let (definition, type_) = self.retrieve_definition();
 
+        // Static variables are symbols of the object file, SEE
+        // `symbols::build_table()`
+        let mut scope = self.data().current_scope.clone();
+        scope.push(definition.id);
+
+        let is_initialized = definition.value.is_some();
         let static_variable = Variable::static_(
-            self.data().current_token.to_string(),
-            type_.unwrap(),
-            current_value,
+            scope.to_string(),
+            type_,
+            definition.value.unwrap_or("0".to_string()),
         );
-        self.add_static_variable(static_variable);
+        self.add_static_variable(static_variable, is_initialized);
     }
 
-    fn when_variable(&mut self, next_tokens: Vec<Token>) 
+    fn when_variable(&mut self, _next_tokens: Vec<Token>) 
     where Self: base::Compiler 
     {
-        let (type_, current_value) = 
-            self.retrieve_variable_info(next_tokens);
-        
-        self.data().i_variable_stack 
-            += type_.as_ref().unwrap().to_usize();
+        let (definition, type_) = self.retrieve_definition();
+
+        // A string is stored with all the characters of its literal
+        let size = match definition.value.as_ref().map(| x | Expression::parse(x)) {
+            Some(Ok(Expression::Literal(Literal::Str(text)))) if type_ == Type::Str => {
+                string_size(&text)
+            }
+            _ => type_.to_usize(),
+        };
+        let position = self.allocate(size, type_.alignment());
 
         let variable = Variable::new(
-            self.data().current_token.to_string(),
-            type_.unwrap(),
-            current_value,
-            self.data().i_variable_stack.clone()
+            definition.id,
+            type_,
+            definition.value.unwrap_or("0".to_string()),
+            position
         );
         self.add_variable(variable);
     }
 
-    fn retrieve_variable_info(
-        &mut self, 
-        next_tokens: Vec<Token>
-    ) -> (Option<Type>, String) 
+    /// The variable definition of the current line, parsed from the source
+    /// code because a value can be an expression \
+    /// A local variable without written type has the type of its value
+    fn retrieve_definition(&mut self) -> (Definition, Type)
     where Self: base::Compiler
     {
-        let mut type_: Option<Type> = None;
-        let mut current_value = "0".to_string();
-
-        let mut previous_token = Token::None;
-        for token in next_tokens.iter() {
-            match previous_token {
-                Token::Assign => {
-                    current_value = match token {
-                        Token::Other(value_as_string) => value_as_string,
-                        _ => panic!(), // never happens
-                    }.to_string();
-                }
-                Token::TypeDef => {
-                    type_ = Some(Type::from_string(match token {
-                        Token::Other(type_as_string) => type_as_string,
-                        _ => panic!(), // never happens
-                    }.to_string()));
-                }
-                Token::None => {} // first token
-                Token::Other(_variable_id) => {} 
-                _ => panic!() // never happens
-            }
-            previous_token = token.clone();
-        }
+        let line_i = self.data().current_line_i;
+        let definition = Definition::parse(self.data().lines[line_i].rest());
 
-        (type_, current_value)
+        let type_ = definition.infer_type(&*self.data())
+            .unwrap(); // checked by "types"
+        (definition, type_)
     }
 
-    fn when_print(&mut self, next_tokens: Vec<Token>) 
+    /// The message is parsed from the source code because the tokens lost
+    /// the braces of the format strings, "println" ends it by a new line \
+    /// SEE `checking::syntax` and `checking::types` for its validation
+    fn when_print(&mut self, _next_tokens: Vec<Token>) 
     where Self: base::Compiler
     {
-        if next_tokens.len() == 0 {
-            self.print(String::new());
-        }
-
-        let mut to_print: Vec<String> = vec![];
-        for token in next_tokens.iter() {
-            match token {
-                // It could be a number, a `Other` does not mean that it's a 
-                // string object
-                Token::Other(x) => to_print.push(x.to_string()),
-                _ => panic!(), // never happens
-            }
-        }
+        let line_i = self.data().current_line_i;
+        let mut format = match self.data().lines[line_i].rest() {
+            "" => Format { pieces: vec![] },
+            message => Format::parse(message).unwrap(), // checked by "syntax"
+        };
 
-        for x in to_print {
-            self.print(x);
+        if source::is_header(&self.data().parsed[line_i], source::PRINTLN) {
+            format.pieces.push(Piece::Text("\\n".to_string()));
         }
+        self.print(&format);
     }
 
     fn when_exit(&mut self, next_tokens: Vec<Token>)
@@ -4,19 +4,32 @@
 
 use std::collections::HashMap as Dict;
 use std::fs::File;
+use std::rc::Rc;
 
-use jup::{
-    parser::Parser, 
-    tokens::Token,
-};
+use jup::tokens::Token;
 
 use x64asm::formatter::Formatter;
 
+use checking::{
+    data::ProjectData,
+    registry::Selection,
+    source::Line,
+    symbols::{Resolution, SymbolKind},
+};
+
 use objects::{
+    enumeration::Enum,
+    expression::Context,
+    function::Function,
+    structure::Struct,
+    type_::Type,
     variable::Variable,
 };
 
-use crate::scope::Scope;
+use crate::{
+    block::Block,
+    scope::Scope,
+};
 
 /// Important information given to the compiler
 pub struct CompilerData {
@@ -24,15 +37,75 @@ pub struct CompilerData {
 
     pub sources: Vec<String>,
     pub options: Dict<String, String>,
+    pub checker_selection: Selection,
+    /// If the array indexes known at runtime are checked
+    pub bounds_checks: bool,
+    /// If the generated assembly is written to the standard output
+    pub emit: bool,
     
-    pub parser: Option<Parser>,
+    pub project: Rc<ProjectData>,
+    pub parsed: Vec<Vec<Token>>,
+    pub lines: Vec<Line>,
     pub asm_formatter: Formatter,
 
     pub current_source: String,
     pub current_scope: Scope,
     pub current_line: Vec<Token>,
+    pub current_line_i: usize,
     pub current_token: Token,
+    pub blocks: Vec<Block>,
+    /// Label of the end of the condition whose branch was just closed, taken
+    /// by its next branch "elif" or "else"
+    pub condition_end: Option<String>,
+    /// Symbols of the other object files already declared in the current one
+    pub externs: Vec<String>,
+    /// Counter making the generated labels unique, SEE `Compiler::new_label()`
+    pub i_label: usize,
     //                        id
     pub variable_stack: Dict<String, Variable>,
+    /// Bytes of the local variables allocated in the stack frame, SEE
+    /// `Compiler::allocate()`
     pub i_variable_stack: usize,
+    /// Bytes pushed under the stack frame while evaluating the current
+    /// statement
+    pub pushed: usize,
+}
+
+/// The identifiers used by the expressions of the current function are its
+/// local variables and the static variables and constants of the project
+impl Context for CompilerData {
+    fn type_of(&self, identifier: &str) -> Option<Type> {
+        if let Some(variable) = self.variable_stack.get(identifier) {
+            return Some(variable.type_().clone());
+        }
+
+        match self.project.symbols.resolve(identifier, &self.current_scope.module()) {
+            Resolution::Found(symbol) => match symbol.kind {
+                SymbolKind::Static(variable) => Some(variable.type_().clone()),
+                SymbolKind::Const(constant) => Some(constant.type_),
+                _ => None,
+            }
+            _ => None,
+        }
+    }
+
+    fn struct_(&self, name: &str) -> Option<Struct> {
+        self.project.symbols.struct_(name, &self.current_scope.module())
+    }
+
+    fn enum_(&self, name: &str) -> Option<Enum> {
+        self.project.symbols.enum_(name, &self.current_scope.module())
+    }
+
+    fn constant(&self, name: &str) -> Option<i128> {
+        // A local variable hides the constants
+        if self.variable_stack.contains_key(name) {
+            return None;
+        }
+        self.project.symbols.constant(name, &self.current_scope.module())?.value
+    }
+
+    fn function(&self, name: &str) -> Option<Function> {
+        self.project.symbols.function(name, &self.current_scope.module())
+    }
 }
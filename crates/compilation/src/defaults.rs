@@ -2,8 +2,10 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+/// The entry point is known by the checkers, SEE `checking::entry::ENTRY_POINT`
+pub use checking::entry::ENTRY_POINT;
+
 pub const BUILD_FOLDER: &str = ".junon";
-pub const ENTRY_POINT: &str = "main";
 pub const EXTENSION: &str = "ju";
 pub const EXTENSION_COMPLETE: &str = ".ju";
 pub const SCOPE_SEPARATOR: &str = ".";
@@ -16,4 +18,26 @@ pub mod linux_defaults {
 
     pub const START_FILE: &str = "startju.asm";
     pub const START_FUNCTION: &str = "_start";
+
+    /// Registers of the integer and pointer arguments, in the order of the
+    /// System V AMD64 calling convention
+    pub const INTEGER_ARGUMENTS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+    /// Registers of the float arguments
+    pub const SSE_ARGUMENTS: [&str; 8] = [
+        "xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7",
+    ];
+
+    /// Routine of each object file writing the message of an error detected
+    /// at runtime, like an out of bounds index or a division by zero, SEE
+    /// `LinuxCompiler::runtime_error()`
+    pub const RUNTIME_ERROR: &str = "__juc_runtime_error";
+
+    /// Routines of each object file writing the value given by "rax" to the
+    /// standard output : the address of a string ended by a NUL byte, an
+    /// integer or a boolean, SEE `LinuxCompiler::print_value()`
+    pub const PRINT_STRING: &str = "__juc_print_string";
+    pub const PRINT_UNSIGNED: &str = "__juc_print_unsigned";
+    pub const PRINT_SIGNED: &str = "__juc_print_signed";
+    pub const PRINT_HEX: &str = "__juc_print_hex";
+    pub const PRINT_BOOL: &str = "__juc_print_bool";
 }
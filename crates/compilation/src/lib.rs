@@ -3,13 +3,16 @@
 // Copyright (c) Junon, Antonin Hérault
 
 pub mod base;
+pub mod block;
 pub mod caller;
 pub mod data;
 pub mod defaults;
 pub mod linux;
 pub mod scope;
+pub mod symbols;
 
 use std::collections::HashMap as Dict;
+use std::rc::Rc;
 
 use jup::tokens::Token;
 
@@ -17,6 +20,12 @@ use x64asm::formatter::Formatter;
 
 use args::Args;
 
+use checking::{
+    data::ProjectData,
+    registry::Selection,
+    symbols::SymbolTable,
+};
+
 use logging::{
     level::LogLevel,
     log::Log,
@@ -73,23 +82,56 @@ pub fn run_compiler(sources: &Vec<String>, options: &Dict<String, String>) {
     logger.add_log(Log::info(format!("Platform : '{:?}'", platform)));
     logger.interpret();
 
+    let mut checker_selection = Selection::default();
+    Args::when_long_option("only-check", options, | names: Vec<String> | {
+        checker_selection.only = names;
+    });
+    Args::when_long_option("skip-check", options, | names: Vec<String> | {
+        checker_selection.skip = names;
+    });
+
+    let mut bounds_checks = true;
+    Args::when_long_option("no-bounds-checks", options, | _ | {
+        bounds_checks = false;
+    });
+
+    let mut emit = false;
+    Args::when_long_option("emit", options, | _ | {
+        emit = true;
+    });
+
     // Set important information for the compiler
     let data = CompilerData {
         is_library,
 
         sources: sources.clone(),
         options: options.clone(),
-
-        parser: None,
+        checker_selection,
+        bounds_checks,
+        emit,
+
+        project: Rc::new(ProjectData {
+            is_library,
+            sources: vec![],
+            symbols: SymbolTable::new(),
+        }),
+        parsed: vec![],
+        lines: vec![],
         asm_formatter: Formatter::new(false),
 
         current_source: String::new(),
         current_scope: Scope::new(),
         current_line: vec![],
+        current_line_i: 0,
         current_token: Token::None,
+        blocks: vec![],
+        condition_end: None,
 
+        externs: vec![],
+        i_label: 0,
         variable_stack: Dict::new(),
         i_variable_stack: 0,
+        pushed: 0,
     };
 
     // Run the right compiler according to the platform
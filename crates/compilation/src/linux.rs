@@ -2,7 +2,8 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
-use std::fs::File;
+use std::collections::HashMap as Dict;
+use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
 
@@ -19,8 +20,13 @@ use x64asm::{
 
 use args::Args;
 
+use checking::symbols::{Resolution, SymbolKind};
+
 use objects::{
+    expression::{fits, string_size, BinaryOperator, Context, Expression, Literal, UnaryOperator},
+    format::{Format, Piece, Radix},
     function::Function, 
+    structure::{align, Class, Struct},
     type_, type_::Type, 
     variable::Variable
 };
@@ -29,6 +35,7 @@ use platform;
 
 use crate::{
     base::Compiler,
+    block::Counter,
     data::CompilerData,
     defaults::linux_defaults::*,
     defaults::*,
@@ -40,6 +47,25 @@ use crate::{
 pub struct LinuxCompiler {
     data: CompilerData,
     section_data: Vec<Instruction>,
+    /// Read-only strings, like the printed texts and the messages of the
+    /// runtime errors
+    section_rodata: Vec<Instruction>,
+    /// Static variables without value, only their size is reserved
+    section_bss: Vec<Instruction>,
+    /// Label of each read-only string of the current object file, by its
+    /// text, SEE `Self::intern()`
+    strings: Dict<String, String>,
+    /// Label of the size of the stack frame of the current function, defined
+    /// once all its local variables are known, SEE `Self::define_frame_size()`
+    frame_size: Option<String>,
+    /// Position of the address given in "rdi" where the current function
+    /// writes the structure it returns, SEE `returns_in_memory()`
+    return_address: Option<usize>,
+    /// If the current object file needs the routine `RUNTIME_ERROR`
+    uses_runtime_error: bool,
+    /// If the current object file needs the routines writing the printed
+    /// values, SEE `PRINT_STRING`
+    uses_print: bool,
 }
 
 impl LinuxCompiler {
@@ -47,8 +73,1250 @@ impl LinuxCompiler {
         Self {
             data,
             section_data: vec![],
+            section_rodata: vec![],
+            section_bss: vec![],
+            strings: Dict::new(),
+            frame_size: None,
+            return_address: None,
+            uses_runtime_error: false,
+            uses_print: false,
+        }
+    }
+
+    /// Initialized static variable, SEE `Self::add_static_variable()`
+    fn add_data(&mut self, variable: &Variable) {
+        let mut init_value: String = variable.current_value().clone();
+        let type_ = variable.type_().clone();
+
+        if type_.alignment() > 1 {
+            self.section_data.push(
+                i!(Expression(format!("align {}", type_.alignment())))
+            );
+        }
+
+        if type_ == Type::Str {
+            // Auto terminate strings by NULL character
+            init_value = format!("`{}`, 0", &init_value[1..init_value.len() - 1]);
+
+            self.section_data.push(
+                i!(label!(variable.id()), type_.to_asm_operand(), Op::Expression(init_value))
+            );
+            return;
+        }
+
+        // Written as the list of the scalar values
+        let scalars = type_.scalars();
+        let values = match Expression::parse(&init_value) {
+            Ok(expression) if expression.is_constant(&type_, &self.data) => {
+                data_values(&expression, &type_, &self.data)
+            }
+            _ => vec![init_value],
+        };
+
+        // The consecutive values of the same size are defined together, the
+        // padding of the structures is filled by zeros
+        let mut groups: Vec<(usize, Vec<String>)> = vec![];
+        let mut position = 0;
+        for ((offset, scalar), value) in scalars.iter().zip(values) {
+            if *offset > position {
+                groups.push((1, vec!["0".to_string(); offset - position]));
+            }
+            position = offset + scalar.to_usize();
+
+            match groups.last_mut() {
+                Some((size, values)) if *size == scalar.to_usize() => values.push(value),
+                _ => groups.push((scalar.to_usize(), vec![value])),
+            }
+        }
+        if type_.to_usize() > position {
+            groups.push((1, vec!["0".to_string(); type_.to_usize() - position]));
+        }
+
+        let is_repeated = | values: &Vec<String> | {
+            values.len() > 1 && values.iter().all(| x | *x == values[0])
+        };
+
+        if groups.len() == 1 && !is_repeated(&groups[0].1) {
+            self.section_data.push(
+                i!(
+                    label!(variable.id()), 
+                    scalars[0].1.to_asm_operand(), 
+                    Op::Expression(groups[0].1.join(", "))
+                )
+            );
+            return;
+        }
+
+        self.section_data.push(i!(label!(variable.id())));
+        for (size, values) in groups {
+            self.section_data.push(i!(Expression(match is_repeated(&values) {
+                true => format!("times {} {} {}", values.len(), directive_name(size), values[0]),
+                false => format!("{} {}", directive_name(size), values.join(", ")),
+            })));
+        }
+    }
+
+    /// Static variable without value, filled by zeros when the program is
+    /// loaded, SEE `Self::add_static_variable()`
+    fn add_bss(&mut self, variable: &Variable) {
+        let type_ = variable.type_().clone();
+
+        if type_.alignment() > 1 {
+            self.section_bss.push(
+                i!(Expression(format!("alignb {}", type_.alignment())))
+            );
+        }
+
+        // A scalar value is reserved as one item of its size, an array or a
+        // structure as its bytes
+        let (size, count) = match type_.is_aggregate() {
+            true => (1, type_.to_usize()),
+            false => (type_.to_usize(), 1),
+        };
+        self.section_bss.push(
+            i!(
+                label!(variable.id()),
+                Op::Expression(format!("{} {}", reserve_directive_name(size), count))
+            )
+        );
+    }
+
+    /// Label of the read-only string `text`, defined once in the object file
+    /// however many times it's used \
+    /// The text is written between backquotes, so its escape sequences like
+    /// "\n" are understood by the assembler
+    fn intern(&mut self, text: &str) -> String {
+        if let Some(text_id) = self.strings.get(text) {
+            return text_id.clone();
+        }
+
+        let text_id = self.new_label("string");
+        self.add_rodata(&text_id, text);
+        self.strings.insert(text.to_string(), text_id.clone());
+        text_id
+    }
+
+    /// Read-only string ended by a NULL character
+    fn add_rodata(&mut self, id: &String, text: &str) {
+        self.section_rodata.push(
+            i!(label!(id), Type::Str.to_asm_operand(), Op::Expression(format!("`{}`, 0", text)))
+        );
+    }
+
+    /// Address and type of a local or static variable
+    fn address_of(&mut self, id: &String) -> (String, Type) {
+        if let Some(variable) = self.data().variable_stack.get(id) {
+            return (
+                format!("[rbp-{}]", variable.stack_pos()),
+                variable.type_().clone()
+            );
+        }
+
+        let module = self.data().current_scope.module();
+        let project = self.data().project.clone();
+
+        match project.symbols.resolve(id, &module) {
+            Resolution::Found(symbol) => {
+                if symbol.module != module {
+                    self.use_extern(symbol.id.clone());
+                }
+                match symbol.kind {
+                    SymbolKind::Static(variable) => (
+                        format!("[{}]", symbol.id),
+                        variable.type_().clone()
+                    ),
+                    _ => panic!(), // never happens, checked by "types"
+                }
+            }
+            _ => panic!(), // never happens, checked by "types"
+        }
+    }
+
+    /// Place the value of a variable in "rax" extended to 64 bits, or in
+    /// "xmm0" for a float \
+    /// An array is not loaded, "rax" is its address
+    fn load(&mut self, id: &String) {
+        let (address, type_) = self.address_of(id);
+
+        match type_.is_aggregate() {
+            true => self.data().asm_formatter.add_instruction(
+                i!(Expression("lea".to_string()), reg!(Rax), Op::Expression(address))
+                    .with_comment(id.to_string())
+                    .clone()
+            ),
+            false => self.load_from(address, &type_, id.to_string()),
+        }
+    }
+
+    /// Same as `Self::load()` but from any address, like "[rax]"
+    fn load_from(&mut self, address: String, type_: &Type, comment: String) {
+        let address = Op::Expression(format!("{} {}", type_.operand_size(), address));
+
+        // The smaller integers are extended according to their sign, writing
+        // in "eax" already clears the upper part of "rax"
+        self.data().asm_formatter.add_instruction(
+            match (type_.to_usize(), type_.is_signed()) {
+                _ if type_.is_float() => i!(
+                    Expression(format!("mov{}", sse_suffix(type_))),
+                    Op::Expression("xmm0".to_string()),
+                    address
+                ),
+                (8, _) => i!(Mov, reg!(Rax), address),
+                (4, true) => i!(Expression("movsxd".to_string()), reg!(Rax), address),
+                (4, false) => i!(Mov, Op::Expression("eax".to_string()), address),
+                (_, true) => i!(Expression("movsx".to_string()), reg!(Rax), address),
+                (_, false) => i!(Expression("movzx".to_string()), reg!(Rax), address),
+            }
+            .with_comment(comment)
+            .clone()
+        );
+    }
+
+    /// Place the value of the counter of a loop "for" in "rax" and the end of
+    /// its range in "rcx", both extended to 64 bits
+    fn load_counter(&mut self, counter: &Counter) {
+        let end = counter.end.clone();
+        self.load_from(format!("[rbp-{}]", end.stack_pos()), end.type_(), end.id().to_string());
+        self.data().asm_formatter.add_instruction(i!(Mov, reg!(Rcx), reg!(Rax)));
+
+        let variable = counter.variable.clone();
+        self.load_from(
+            format!("[rbp-{}]", variable.stack_pos()),
+            variable.type_(),
+            variable.id().to_string()
+        );
+    }
+
+    /// Place the address of a variable or of an element of an array in "rax"
+    /// and give the type of the value placed there
+    fn evaluate_address(&mut self, target: &Expression) -> Type {
+        match target {
+            Expression::Identifier(id) => {
+                let (address, type_) = self.address_of(id);
+                self.data().asm_formatter.add_instruction(
+                    i!(Expression("lea".to_string()), reg!(Rax), Op::Expression(address))
+                        .with_comment(id.to_string())
+                        .clone()
+                );
+                type_
+            }
+            Expression::Index(base, index) => {
+                let (element, count) = match self.evaluate_address(base) {
+                    Type::Array(element, count) => (*element, count),
+                    _ => panic!(), // never happens, checked by "types"
+                };
+                self.push(reg!(Rax));
+
+                let index_type = index.type_(&self.data)
+                    .unwrap(); // checked by "types"
+                self.evaluate(index, &index_type);
+
+                // A literal index is checked at compile-time
+                if self.data().bounds_checks && !index.is_literal() {
+                    self.check_bounds(count);
+                }
+
+                self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Mov, reg!(Rcx), Op::Expression(element.to_usize().to_string())),
+                    i!(Expression("imul".to_string()), reg!(Rax), reg!(Rcx)),
+                ]);
+                self.pop(reg!(Rcx));
+                self.data().asm_formatter.add_instruction(
+                    i!(Expression("add".to_string()), reg!(Rax), reg!(Rcx))
+                );
+                element
+            }
+            Expression::Field(base, field) => {
+                // A static variable of another module, like "lib.value"
+                if base.type_(&self.data).is_err() {
+                    let id = target.qualified_name()
+                        .unwrap(); // checked by "types"
+                    return self.evaluate_address(&Expression::Identifier(id));
+                }
+
+                // Through a pointer, its value is the address of the structure
+                let base_type = base.type_(&self.data)
+                    .unwrap(); // checked by "types"
+                let struct_ = match base_type {
+                    Type::Pointer(ref pointee) => {
+                        self.evaluate(base, &base_type);
+                        pointee.resolve(&self.data)
+                    }
+                    _ => self.evaluate_address(base),
+                };
+                let struct_ = match struct_ {
+                    Type::Struct(struct_) => struct_,
+                    _ => panic!(), // never happens, checked by "types"
+                };
+                let field = struct_.field(field)
+                    .unwrap(); // checked by "types"
+
+                if field.offset != 0 {
+                    self.data().asm_formatter.add_instruction(
+                        i!(
+                            Expression("add".to_string()),
+                            reg!(Rax),
+                            Op::Expression(field.offset.to_string())
+                        )
+                        .with_comment(target.to_string())
+                        .clone()
+                    );
+                }
+                field.type_.clone()
+            }
+            Expression::Unary(UnaryOperator::Dereference, pointer) => {
+                let type_ = pointer.type_(&self.data)
+                    .unwrap(); // checked by "types"
+                self.evaluate(pointer, &type_);
+
+                match type_ {
+                    Type::Pointer(pointee) => pointee.resolve(&self.data),
+                    _ => panic!(), // never happens, checked by "types"
+                }
+            }
+            // The returned structure is in a slot of the stack frame
+            Expression::Call(_, _) => {
+                let type_ = target.type_(&self.data)
+                    .unwrap(); // checked by "types"
+                self.evaluate(target, &type_);
+                type_
+            }
+            _ => panic!(), // never happens, checked by "syntax"
+        }
+    }
+
+    /// Push the address placed on the top of the stack plus `offset`, for
+    /// storing an element or a field with `Self::store()`
+    fn push_offset(&mut self, offset: usize) {
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Mov, reg!(Rax), Op::Expression("[rsp]".to_string())),
+            i!(
+                Expression("add".to_string()),
+                reg!(Rax),
+                Op::Expression(offset.to_string())
+            ),
+        ]);
+        self.push(reg!(Rax));
+    }
+
+    /// Push a value of 8 bytes, counted until its pop to keep the stack
+    /// aligned at the calls, SEE `Compiler::call_function()`
+    fn push(&mut self, operand: Op) {
+        self.data().pushed += 8;
+        self.data().asm_formatter.add_instruction(i!(Push, operand));
+    }
+
+    fn pop(&mut self, operand: Op) {
+        self.data().pushed -= 8;
+        self.data().asm_formatter.add_instruction(i!(Pop, operand));
+    }
+
+    /// Give its value to the label of the stack frame size of the function
+    /// compiled last, all its variables are allocated \
+    /// The size is a multiple of 16, the stack stays aligned for the calls
+    fn define_frame_size(&mut self) {
+        if let Some(label) = self.frame_size.take() {
+            let size = align(self.data().i_variable_stack, 16);
+            self.data().asm_formatter.add_instruction(
+                i!(Expression(format!("{} equ {}", label, size)))
+            );
+        }
+    }
+
+    /// Jump to the routine `RUNTIME_ERROR` when the index in "rax" is not
+    /// lower than `count` \
+    /// A negative index is seen as a big unsigned integer
+    fn check_bounds(&mut self, count: usize) {
+        self.data().asm_formatter.add_instruction(
+            i!(Expression("cmp".to_string()), reg!(Rax), Op::Expression(count.to_string()))
+        );
+        self.runtime_error("jb", "Index out of bounds");
+    }
+
+    /// Jump to the routine `RUNTIME_ERROR` when the divisor in "rcx" is zero
+    fn check_division(&mut self) {
+        self.data().asm_formatter.add_instruction(
+            i!(Expression("test".to_string()), reg!(Rcx), reg!(Rcx))
+        );
+        self.runtime_error("jnz", "Division by zero");
+    }
+
+    /// Jump to the routine `RUNTIME_ERROR` with the message `error` giving the
+    /// current line, unless the conditional jump `valid` is taken after the
+    /// check already written
+    fn runtime_error(&mut self, valid: &str, error: &str) {
+        let valid_label = self.new_label("valid");
+
+        let line_i = self.data().current_line_i;
+        let message = format!(
+            "{}{}:{}: {}\\n",
+            self.data().current_scope.module(),
+            EXTENSION_COMPLETE,
+            line_i + 1,
+            error
+        );
+        let message_id = self.intern(&message);
+        self.uses_runtime_error = true;
+
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Expression(valid.to_string()), Op::Label(valid_label.clone())),
+            i!(Mov, reg!(Rsi), Op::Label(message_id)),
+            // "\n" is one character
+            i!(Mov, reg!(Rdx), Op::Expression((message.len() - 1).to_string())),
+            i!(Expression("jmp".to_string()), Op::Label(RUNTIME_ERROR.to_string())),
+            i!(label!(&valid_label)),
+        ]);
+    }
+
+    /// Initialize a local array or structure, filled by zeros when it has no
+    /// value
+    fn initialize_aggregate(&mut self, variable: &Variable) {
+        self.data().asm_formatter.add_instruction(
+            i!(
+                Expression("lea".to_string()),
+                reg!(Rax),
+                Op::Expression(format!("[rbp-{}]", variable.stack_pos()))
+            )
+            .with_comment(format!("{}: {}", variable.id(), variable.type_()))
+            .clone()
+        );
+        self.push(reg!(Rax));
+
+        match Expression::parse(variable.current_value()) {
+            Ok(Expression::Literal(_)) | Err(_) => {
+                self.pop(reg!(Rdi));
+                self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Xor, reg!(Rax), reg!(Rax)),
+                    i!(Mov, reg!(Rcx), Op::Expression(variable.type_().to_usize().to_string())),
+                    i!(Expression("rep".to_string()), Op::Expression("stosb".to_string())),
+                ]);
+            }
+            Ok(value) => self.store(&value, variable.type_()),
         }
     }
+
+    /// Write the returned structure where the caller reads it : at the address
+    /// it gave, or in a slot whose eightbytes are then loaded in the return
+    /// registers \
+    /// The structure is filled with zeros when there is no value
+    fn return_struct(&mut self, value: Option<&Expression>, struct_: &Struct) {
+        let destination = match self.return_address {
+            Some(position) => i!(
+                Mov,
+                reg!(Rax),
+                Op::Expression(format!("[rbp-{}]", position))
+            ),
+            None => {
+                let position = self.allocate(align(struct_.size, 8), 8);
+                i!(
+                    Expression("lea".to_string()),
+                    reg!(Rax),
+                    Op::Expression(format!("[rbp-{}]", position))
+                )
+            }
+        };
+        self.data().asm_formatter.add_instruction(destination.clone());
+        self.push(reg!(Rax));
+
+        let type_ = Type::Struct(Box::new(struct_.clone()));
+        match value {
+            Some(value) => self.store(value, &type_),
+            None => {
+                self.pop(reg!(Rdi));
+                self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Xor, reg!(Rax), reg!(Rax)),
+                    i!(Mov, reg!(Rcx), Op::Expression(struct_.size.to_string())),
+                    i!(Expression("rep".to_string()), Op::Expression("stosb".to_string())),
+                ]);
+            }
+        }
+
+        // The address is also returned in "rax"
+        self.data().asm_formatter.add_instruction(destination);
+        let classes = match struct_.classes() {
+            Some(classes) => classes,
+            None => return,
+        };
+
+        self.data().asm_formatter.add_instruction(i!(Mov, reg!(Rcx), reg!(Rax)));
+        for (i, register) in return_registers(&classes).iter().enumerate() {
+            let address = Op::Expression(format!("qword [rcx+{}]", i * 8));
+            self.data().asm_formatter.add_instruction(
+                match register.starts_with("xmm") {
+                    true => i!(
+                        Expression("movq".to_string()),
+                        Op::Expression(register.to_string()),
+                        address
+                    ),
+                    false => i!(Mov, Op::Expression(register.to_string()), address),
+                }
+            );
+        }
+    }
+
+    /// Store the value at the address placed on the top of the stack, the
+    /// address is popped \
+    /// An array or a structure is stored element by element or field by field
+    /// when it's written as a literal, or copied from the address given by its
+    /// evaluation
+    fn store(&mut self, value: &Expression, type_: &Type) {
+        match (value, type_) {
+            (Expression::Array(elements), Type::Array(element, _)) => {
+                for (i, x) in elements.iter().enumerate() {
+                    self.push_offset(i * element.to_usize());
+                    self.store(x, element);
+                }
+                self.data().pushed -= 8;
+                self.data().asm_formatter.add_instruction(
+                    i!(Expression("add".to_string()), reg!(Rsp), Op::Literal(8))
+                );
+            }
+            (Expression::Struct(_, fields), Type::Struct(struct_)) => {
+                for (id, x) in fields.iter() {
+                    let field = struct_.field(id)
+                        .unwrap(); // checked by "types"
+                    self.push_offset(field.offset);
+                    self.store(x, &field.type_);
+                }
+                self.data().pushed -= 8;
+                self.data().asm_formatter.add_instruction(
+                    i!(Expression("add".to_string()), reg!(Rsp), Op::Literal(8))
+                );
+            }
+            (Expression::Repeat(x, count), Type::Array(element, _)) => {
+                if element.is_aggregate() {
+                    let elements = vec![*x.clone(); *count];
+                    self.store(&Expression::Array(elements), type_);
+                    return;
+                }
+
+                // The bits of a float are stored like an integer
+                self.evaluate(x, element);
+                match **element {
+                    Type::F32 => self.data().asm_formatter.add_instruction(i!(
+                        Expression("movd".to_string()),
+                        Op::Expression("eax".to_string()),
+                        Op::Expression("xmm0".to_string())
+                    )),
+                    Type::F64 => self.data().asm_formatter.add_instruction(i!(
+                        Expression("movq".to_string()),
+                        reg!(Rax),
+                        Op::Expression("xmm0".to_string())
+                    )),
+                    _ => {}
+                }
+
+                let stos = format!("stos{}", &directive_name(element.to_usize())[1..]);
+                self.pop(reg!(Rdi));
+                self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Mov, reg!(Rcx), Op::Expression(count.to_string())),
+                    i!(Expression("rep".to_string()), Op::Expression(stos)),
+                ]);
+            }
+            _ if type_.is_aggregate() => {
+                self.evaluate(value, type_);
+                self.data().asm_formatter.add_instruction(i!(Mov, reg!(Rsi), reg!(Rax)));
+                self.pop(reg!(Rdi));
+                self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Mov, reg!(Rcx), Op::Expression(type_.to_usize().to_string())),
+                    i!(Expression("rep".to_string()), Op::Expression("movsb".to_string())),
+                ]);
+            }
+            _ => {
+                self.evaluate(value, type_);
+                self.pop(reg!(Rcx));
+                self.data().asm_formatter.add_instruction(
+                    match type_.is_float() {
+                        true => i!(
+                            Expression(format!("mov{}", sse_suffix(type_))),
+                            Op::Expression(format!("{} [rcx]", type_.operand_size())),
+                            Op::Expression("xmm0".to_string())
+                        ),
+                        false => i!(
+                            Mov,
+                            Op::Expression(format!("{} [rcx]", type_.operand_size())),
+                            Op::Expression(rax_part(type_.to_usize()).to_string())
+                        ),
+                    }
+                );
+            }
+        }
+    }
+
+    /// Place a float literal in "xmm0", through "rax" because SSE has no
+    /// immediate operands
+    fn load_float(&mut self, value: f64, type_: &Type) {
+        let (bits, register, move_) = match *type_ {
+            Type::F32 => ((value as f32).to_bits() as u64, "eax", "movd"),
+            _ => (value.to_bits(), "rax", "movq"),
+        };
+
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(
+                Mov,
+                Op::Expression(register.to_string()),
+                Op::Expression(format!("{:#x}", bits))
+            )
+            .with_comment(format!("{:?}", value))
+            .clone(),
+            i!(
+                Expression(move_.to_string()),
+                Op::Expression("xmm0".to_string()),
+                Op::Expression(register.to_string())
+            ),
+        ]);
+    }
+
+    /// Evaluate the two operands of a binary operation, the left one is
+    /// placed in "rax" and the right one in "rcx", or in "xmm0" and "xmm1"
+    /// for the floats
+    fn evaluate_operands(&mut self, lhs: &Expression, rhs: &Expression, type_: &Type) {
+        if !type_.is_float() {
+            self.evaluate(lhs, type_);
+            self.push(reg!(Rax));
+            self.evaluate(rhs, type_);
+
+            self.data().asm_formatter.add_instruction(i!(Mov, reg!(Rcx), reg!(Rax)));
+            self.pop(reg!(Rax));
+            return;
+        }
+
+        // There is no "push" for the "xmm" registers
+        let move_ = format!("mov{}", sse_suffix(type_));
+        let top = Op::Expression(format!("{} [rsp]", type_.operand_size()));
+
+        self.evaluate(lhs, type_);
+        self.data().pushed += 8;
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Expression("sub".to_string()), reg!(Rsp), Op::Literal(8)),
+            i!(Expression(move_.clone()), top.clone(), Op::Expression("xmm0".to_string())),
+        ]);
+        self.evaluate(rhs, type_);
+        self.data().pushed -= 8;
+
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(
+                Expression(move_.clone()),
+                Op::Expression("xmm1".to_string()),
+                Op::Expression("xmm0".to_string())
+            ),
+            i!(Expression(move_), Op::Expression("xmm0".to_string()), top),
+            i!(Expression("add".to_string()), reg!(Rsp), Op::Literal(8)),
+        ]);
+    }
+
+    /// Evaluate "and" and "or" : the right operand is not evaluated when the
+    /// left one already gives the result
+    fn evaluate_logical(
+        &mut self,
+        operator: &BinaryOperator,
+        lhs: &Expression,
+        rhs: &Expression
+    ) {
+        let end_label = self.new_label(operator.symbol());
+
+        // The left operand is the result when it's "false" for "and" and
+        // "true" for "or"
+        let jump = match *operator {
+            BinaryOperator::And => "je",
+            _ => "jne",
+        };
+
+        self.evaluate(lhs, &Type::Bool);
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Expression("cmp".to_string()), reg!(Rax), Op::Literal(0)),
+            i!(Expression(jump.to_string()), Op::Label(end_label.clone())),
+        ]);
+        self.evaluate(rhs, &Type::Bool);
+        self.data().asm_formatter.add_instruction(i!(label!(&end_label)));
+    }
+
+    /// Evaluate an addition or a subtraction with a pointer, the integer is a
+    /// number of pointed values and the difference of two pointers too
+    fn evaluate_pointer_arithmetic(
+        &mut self,
+        operator: &BinaryOperator,
+        lhs: &Expression,
+        rhs: &Expression
+    ) {
+        let lhs_type = lhs.type_(&self.data)
+            .unwrap(); // checked by "types"
+        let rhs_type = rhs.type_(&self.data)
+            .unwrap(); // checked by "types"
+
+        // The pointer is placed in "rax" and the integer in "rcx"
+        let (pointer, integer, pointer_type, integer_type) = match lhs_type.is_pointer() {
+            true => (lhs, rhs, lhs_type, rhs_type),
+            false => (rhs, lhs, rhs_type, lhs_type),
+        };
+        let size = match pointer_type {
+            Type::Pointer(ref pointee) => pointee.resolve(&self.data).to_usize(),
+            _ => panic!(), // never happens, checked by "types"
+        };
+
+        if integer_type.is_pointer() {
+            self.evaluate_operands(pointer, integer, &pointer_type);
+            self.data().asm_formatter.add_instructions(&mut vec![
+                i!(Expression("sub".to_string()), reg!(Rax), reg!(Rcx)),
+                i!(Mov, reg!(Rcx), Op::Expression(size.to_string())),
+                i!(Expression("cqo".to_string())),
+                i!(Expression("idiv".to_string()), reg!(Rcx)),
+            ]);
+            return;
+        }
+
+        self.evaluate(pointer, &pointer_type);
+        self.push(reg!(Rax));
+        self.evaluate(integer, &integer_type);
+
+        let mnemonic = match *operator {
+            BinaryOperator::Add => "add",
+            _ => "sub",
+        };
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Expression("imul".to_string()), reg!(Rax), Op::Expression(size.to_string())),
+            i!(Mov, reg!(Rcx), reg!(Rax)),
+        ]);
+        self.pop(reg!(Rax));
+        self.data().asm_formatter.add_instruction(
+            i!(Expression(mnemonic.to_string()), reg!(Rax), reg!(Rcx))
+        );
+    }
+
+    /// Evaluate a comparison, the result in "rax" is "1" when it's true and
+    /// "0" otherwise \
+    /// A comparison with a NaN float is false, except for "!="
+    fn evaluate_comparison(
+        &mut self,
+        operator: &BinaryOperator,
+        lhs: &Expression,
+        rhs: &Expression
+    ) {
+        let type_ = Expression::operands_type(lhs, rhs, &self.data)
+            .unwrap(); // checked by "types"
+        self.evaluate_operands(lhs, rhs, &type_);
+        let condition = self.compare(operator, &type_);
+
+        self.data().asm_formatter.add_instruction(i!(
+            Expression(format!("set{}", condition_code(&condition, !type_.is_signed()))),
+            reg!(Al)
+        ));
+
+        // The parity flag is set when a float is NaN
+        if type_.is_float() {
+            let parity = match operator {
+                BinaryOperator::Equal => Some(("setnp", "and")),
+                BinaryOperator::NotEqual => Some(("setp", "or")),
+                _ => None,
+            };
+
+            if let Some((set, combine)) = parity {
+                self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Expression(set.to_string()), Op::Expression("cl".to_string())),
+                    i!(
+                        Expression(combine.to_string()),
+                        reg!(Al),
+                        Op::Expression("cl".to_string())
+                    ),
+                ]);
+            }
+        }
+
+        self.data().asm_formatter.add_instruction(
+            i!(Expression("movzx".to_string()), reg!(Rax), reg!(Al))
+        );
+    }
+
+    /// Compare "rax" with "rcx", or "xmm0" with "xmm1" for the floats, the
+    /// returned comparison gives the condition code that reads the flags \
+    /// The floats are compared with "ucomiss" or "ucomisd", their operands are
+    /// swapped for "<" and "<=" so that the "above" conditions, false when a
+    /// float is NaN, are used
+    fn compare(&mut self, operator: &BinaryOperator, type_: &Type) -> BinaryOperator {
+        if !type_.is_float() {
+            self.data().asm_formatter.add_instruction(
+                i!(Expression("cmp".to_string()), reg!(Rax), reg!(Rcx))
+            );
+            return *operator;
+        }
+
+        let (condition, operands) = match operator {
+            BinaryOperator::Less => (BinaryOperator::Greater, ["xmm1", "xmm0"]),
+            BinaryOperator::LessEqual => (BinaryOperator::GreaterEqual, ["xmm1", "xmm0"]),
+            _ => (*operator, ["xmm0", "xmm1"]),
+        };
+
+        self.data().asm_formatter.add_instruction(i!(
+            Expression(format!("ucomi{}", sse_suffix(type_))),
+            Op::Expression(operands[0].to_string()),
+            Op::Expression(operands[1].to_string())
+        ));
+        condition
+    }
+
+    /// Evaluate "+", "-", "*", "/" and "%", the result is in "rax" or in
+    /// "xmm0" for the floats \
+    /// An integer division by zero stops the program with an error
+    fn evaluate_arithmetic(
+        &mut self,
+        operator: &BinaryOperator,
+        lhs: &Expression,
+        rhs: &Expression,
+        type_: &Type
+    ) {
+        self.evaluate_operands(lhs, rhs, type_);
+
+        if type_.is_float() {
+            let mnemonic = match *operator {
+                BinaryOperator::Add => "add",
+                BinaryOperator::Subtract => "sub",
+                BinaryOperator::Multiply => "mul",
+                _ => "div",
+            };
+
+            self.data().asm_formatter.add_instruction(i!(
+                Expression(format!("{}{}", mnemonic, sse_suffix(type_))),
+                Op::Expression("xmm0".to_string()),
+                Op::Expression("xmm1".to_string())
+            ));
+            return;
+        }
+
+        let mut instructions = match *operator {
+            BinaryOperator::Add => vec![
+                i!(Expression("add".to_string()), reg!(Rax), reg!(Rcx)),
+            ],
+            BinaryOperator::Subtract => vec![
+                i!(Expression("sub".to_string()), reg!(Rax), reg!(Rcx)),
+            ],
+            BinaryOperator::Multiply => vec![
+                i!(Expression("imul".to_string()), reg!(Rax), reg!(Rcx)),
+            ],
+            // "rdx:rax" is divided, "rdx" is the sign extension of "rax" or
+            // zero
+            _ if type_.is_signed() => vec![
+                i!(Expression("cqo".to_string())),
+                i!(Expression("idiv".to_string()), reg!(Rcx)),
+            ],
+            _ => vec![
+                i!(Xor, reg!(Rdx), reg!(Rdx)),
+                i!(Expression("div".to_string()), reg!(Rcx)),
+            ],
+        };
+
+        let is_division = *operator == BinaryOperator::Divide
+            || *operator == BinaryOperator::Remainder;
+        if is_division {
+            self.check_division();
+        }
+        // The remainder is placed in "rdx"
+        if *operator == BinaryOperator::Remainder {
+            instructions.push(i!(Mov, reg!(Rax), reg!(Rdx)));
+        }
+        self.data().asm_formatter.add_instructions(&mut instructions);
+
+        // Wrapped around like a value of its type
+        self.extend(type_);
+    }
+
+    /// Evaluate "&", "|", "^", "<<" and ">>", the result is in "rax" \
+    /// The number of bits of a shift is placed in "cl", a right shift keeps
+    /// the sign of a signed integer
+    fn evaluate_bitwise(
+        &mut self,
+        operator: &BinaryOperator,
+        lhs: &Expression,
+        rhs: &Expression,
+        type_: &Type
+    ) {
+        if operator.is_shift() {
+            let count_type = rhs.type_(&self.data)
+                .unwrap(); // checked by "types"
+            self.evaluate(lhs, type_);
+            self.push(reg!(Rax));
+            self.evaluate(rhs, &count_type);
+            self.data().asm_formatter.add_instruction(i!(Mov, reg!(Rcx), reg!(Rax)));
+            self.pop(reg!(Rax));
+        } else {
+            self.evaluate_operands(lhs, rhs, type_);
+        }
+
+        let mnemonic = match *operator {
+            BinaryOperator::BitAnd => "and",
+            BinaryOperator::BitOr => "or",
+            BinaryOperator::BitXor => "xor",
+            BinaryOperator::ShiftLeft => "shl",
+            _ if type_.is_signed() => "sar",
+            _ => "shr",
+        };
+        let count = match operator.is_shift() {
+            true => Op::Expression("cl".to_string()),
+            false => reg!(Rcx),
+        };
+        self.data().asm_formatter.add_instruction(
+            i!(Expression(mnemonic.to_string()), reg!(Rax), count)
+        );
+
+        // The other operations keep the extended bits of their operands
+        if *operator == BinaryOperator::ShiftLeft {
+            self.extend(type_);
+        }
+    }
+
+    /// Write the value of `value` with the routine of its type, the integers
+    /// and the enums are written in decimal unless `radix` asks for
+    /// hexadecimal, the pointers are always written in hexadecimal
+    fn print_value(&mut self, value: &Expression, radix: Radix) {
+        let type_ = value.type_(&self.data).unwrap(); // checked by "types"
+
+        let routine = match type_ {
+            // A string is given by its address
+            Type::Str => {
+                self.evaluate_address(value);
+                PRINT_STRING
+            }
+            Type::Bool => {
+                self.evaluate(value, &type_);
+                PRINT_BOOL
+            }
+            _ if radix == Radix::Hexadecimal || type_.is_pointer() => {
+                self.evaluate(value, &type_);
+                // Only the bits of the type, a negative value is not
+                // written with 64 bits
+                self.zero_extend(&type_);
+                PRINT_HEX
+            }
+            _ => {
+                self.evaluate(value, &type_);
+                match type_.is_signed() {
+                    true => PRINT_SIGNED,
+                    false => PRINT_UNSIGNED,
+                }
+            }
+        };
+
+        self.data().asm_formatter.add_instruction(
+            i!(Expression("call".to_string()), Op::Label(routine.to_string())).with_comment(value.to_string()).clone()
+        );
+    }
+
+    /// Clear the bits of "rax" above an unsigned integer of type `type_`, so
+    /// the value stays zero extended to 64 bits
+    fn zero_extend(&mut self, type_: &Type) {
+        let instruction = match type_.to_usize() {
+            1 => i!(Expression("movzx".to_string()), reg!(Rax), reg!(Al)),
+            2 => i!(
+                Expression("movzx".to_string()),
+                reg!(Rax),
+                Op::Expression("ax".to_string())
+            ),
+            4 => i!(
+                Mov,
+                Op::Expression("eax".to_string()),
+                Op::Expression("eax".to_string())
+            ),
+            _ => return,
+        };
+        self.data().asm_formatter.add_instruction(instruction);
+    }
+
+    /// Extend the integer of type `type_` in the lower bits of "rax" to 64
+    /// bits according to its sign, the bits above it are the ones of a result
+    /// that the type cannot hold
+    fn extend(&mut self, type_: &Type) {
+        if !type_.is_signed() {
+            self.zero_extend(type_);
+            return;
+        }
+
+        let instruction = match type_.to_usize() {
+            1 => i!(Expression("movsx".to_string()), reg!(Rax), reg!(Al)),
+            2 => i!(
+                Expression("movsx".to_string()),
+                reg!(Rax),
+                Op::Expression("ax".to_string())
+            ),
+            4 => i!(
+                Expression("movsxd".to_string()),
+                reg!(Rax),
+                Op::Expression("eax".to_string())
+            ),
+            _ => return,
+        };
+        self.data().asm_formatter.add_instruction(instruction);
+    }
+}
+
+/// Routines writing the value given by "rax" to the standard output, the
+/// integers are written from their last digit in a buffer of the stack \
+/// They are called in the middle of a statement so they only change "rax",
+/// "rcx", "rdx", "rsi", "rdi" and the registers clobbered by "syscall"
+fn print_routines() -> String {
+    format!("\
+{string}:
+    mov rsi, rax
+    mov rdx, rax
+.length:
+    cmp byte [rdx], 0
+    je .write
+    inc rdx
+    jmp .length
+.write:
+    sub rdx, rsi
+    mov rax, 1
+    mov rdi, 1
+    syscall
+    ret
+{signed}:
+    test rax, rax
+    jns {unsigned}
+    neg rax
+    push rax
+    push 45 ; '-'
+    mov rsi, rsp
+    mov rdx, 1
+    mov rax, 1
+    mov rdi, 1
+    syscall
+    add rsp, 8
+    pop rax
+{unsigned}:
+    sub rsp, 32
+    lea rsi, [rsp+32]
+    mov rcx, 10
+.digit:
+    xor rdx, rdx
+    div rcx
+    add dl, 48 ; '0'
+    dec rsi
+    mov [rsi], dl
+    test rax, rax
+    jnz .digit
+    jmp {hex}.write
+{hex}:
+    sub rsp, 32
+    lea rsi, [rsp+32]
+.digit:
+    mov rdx, rax
+    and rdx, 15
+    add dl, 48 ; '0'
+    cmp dl, 57 ; '9'
+    jbe .store
+    add dl, 39 ; 'a' - '9' - 1
+.store:
+    dec rsi
+    mov [rsi], dl
+    shr rax, 4
+    jnz .digit
+    sub rsi, 2
+    mov byte [rsi], 48 ; '0'
+    mov byte [rsi+1], 120 ; 'x'
+.write:
+    lea rdx, [rsp+32]
+    sub rdx, rsi
+    mov rax, 1
+    mov rdi, 1
+    syscall
+    add rsp, 32
+    ret
+{bool}:
+    test rax, rax
+    mov rax, {bool}_true
+    jnz {string}
+    mov rax, {bool}_false
+    jmp {string}",
+        string = PRINT_STRING,
+        signed = PRINT_SIGNED,
+        unsigned = PRINT_UNSIGNED,
+        hex = PRINT_HEX,
+        bool = PRINT_BOOL,
+    )
+}
+
+/// Data directive for a value of `size` bytes
+fn directive_name(size: usize) -> &'static str {
+    match size {
+        1 => "db",
+        2 => "dw",
+        4 => "dd",
+        _ => "dq",
+    }
+}
+
+/// Directive reserving values of `size` bytes in the section ".bss"
+fn reserve_directive_name(size: usize) -> &'static str {
+    match size {
+        1 => "resb",
+        2 => "resw",
+        4 => "resd",
+        _ => "resq",
+    }
+}
+
+/// Values written in the data section for a constant expression of type
+/// `type_`, one per scalar value in the order of `Type::scalars()`
+fn data_values(expression: &Expression, type_: &Type, context: &dyn Context) -> Vec<String> {
+    match (expression, type_) {
+        (Expression::Array(elements), Type::Array(element, _)) => elements.iter()
+            .flat_map(| x | data_values(x, element, context))
+            .collect(),
+        (Expression::Repeat(value, count), Type::Array(element, _)) => {
+            data_values(value, element, context).repeat(*count)
+        }
+        // The fields are written in the order of the layout
+        (Expression::Struct(_, fields), Type::Struct(struct_)) => struct_.fields.iter()
+            .flat_map(| field | match fields.iter().find(| (id, _) | *id == field.id) {
+                Some((_, x)) => data_values(x, &field.type_, context),
+                None => panic!(), // never happens, checked by "types"
+            })
+            .collect(),
+        // Computed, like a constant or a variant of an enum
+        _ => match expression.constant_value_as(Some(type_), context) {
+            Ok(value) => vec![value.to_string()],
+            Err(_) => vec![expression.to_string()],
+        },
+    }
+}
+
+/// Part of "rax" for a value of `size` bytes
+fn rax_part(size: usize) -> &'static str {
+    match size {
+        1 => "al",
+        2 => "ax",
+        4 => "eax",
+        _ => "rax",
+    }
+}
+
+/// Part of a general purpose register for a value of `size` bytes \
+/// Example: "rdi" and 4 bytes give "edi", "r8" and 1 byte gives "r8b"
+fn register_part(register: &str, size: usize) -> String {
+    let name = &register[1..];
+    if name.chars().all(| c | c.is_ascii_digit()) {
+        return match size {
+            1 => format!("{}b", register),
+            2 => format!("{}w", register),
+            4 => format!("{}d", register),
+            _ => register.to_string(),
+        };
+    }
+
+    match size {
+        // "al" but "dil"
+        1 if name.ends_with('x') => format!("{}l", &name[..1]),
+        1 => format!("{}l", name),
+        2 => name.to_string(),
+        4 => format!("e{}", name),
+        _ => register.to_string(),
+    }
+}
+
+/// Where an argument is passed by the calling convention
+#[derive(Debug, Clone)]
+enum Location {
+    /// One register per eightbyte of the value
+    Registers(Vec<&'static str>),
+    /// On the stack, at this offset from the first argument passed there
+    Stack(usize),
+}
+
+/// Locations of the arguments of types `types` and the size of the ones
+/// passed on the stack, following the System V AMD64 calling convention \
+/// A value is passed on the stack when it's an array, a structure bigger than
+/// two eightbytes or when there are not enough registers left for it
+fn argument_locations(types: &[Type], return_type: &Option<Type>) -> (Vec<Location>, usize) {
+    let mut integers = INTEGER_ARGUMENTS.iter();
+    let mut sses = SSE_ARGUMENTS.iter();
+    let mut stack_size = 0;
+
+    // The address of the returned structure is passed first, in "rdi"
+    if returns_in_memory(return_type) {
+        integers.next();
+    }
+
+    let locations = types.iter()
+        .map(| type_ | {
+            let classes = match type_ {
+                Type::Struct(struct_) => struct_.classes(),
+                Type::Array(..) => None,
+                _ if type_.is_float() => Some(vec![Class::Sse]),
+                _ => Some(vec![Class::Integer]),
+            };
+
+            let fits_in_registers = | classes: &Vec<Class> | {
+                let needed = classes.iter().filter(| x | **x == Class::Integer).count();
+                needed <= integers.len() && classes.len() - needed <= sses.len()
+            };
+
+            match classes {
+                Some(classes) if fits_in_registers(&classes) => Location::Registers(
+                    classes.iter()
+                        .map(| x | match x {
+                            Class::Integer => *integers.next().unwrap(),
+                            Class::Sse => *sses.next().unwrap(),
+                        })
+                        .collect()
+                ),
+                _ => {
+                    let offset = stack_size;
+                    stack_size += align(type_.to_usize(), 8);
+                    Location::Stack(offset)
+                }
+            }
+        })
+        .collect();
+
+    (locations, stack_size)
+}
+
+/// If a function returning a value of type `return_type` writes it at the
+/// address given by its caller, like a structure bigger than two eightbytes,
+/// otherwise a structure is returned in "rax" and "rdx" or in "xmm0" and
+/// "xmm1" according to the classes of its eightbytes
+fn returns_in_memory(return_type: &Option<Type>) -> bool {
+    matches!(return_type, Some(Type::Struct(struct_)) if struct_.classes().is_none())
+}
+
+/// Registers of the eightbytes of a structure returned in registers, the
+/// integer ones in "rax" then "rdx" and the float ones in "xmm0" then "xmm1"
+fn return_registers(classes: &[Class]) -> Vec<&'static str> {
+    let mut integers = ["rax", "rdx"].iter();
+    let mut sses = ["xmm0", "xmm1"].iter();
+
+    classes.iter()
+        .map(| x | match x {
+            Class::Integer => *integers.next().unwrap(),
+            Class::Sse => *sses.next().unwrap(),
+        })
+        .collect()
+}
+
+/// Condition code of the "set" and jump instructions for a comparison \
+/// The flags set by "ucomiss" and "ucomisd" are read like for an unsigned
+/// comparison, see "compare" for the unordered results
+fn condition_code(operator: &BinaryOperator, unsigned: bool) -> &'static str {
+    match (*operator, unsigned) {
+        (BinaryOperator::Equal, _) => "e",
+        (BinaryOperator::NotEqual, _) => "ne",
+        (BinaryOperator::Less, false) => "l",
+        (BinaryOperator::LessEqual, false) => "le",
+        (BinaryOperator::Greater, false) => "g",
+        (BinaryOperator::GreaterEqual, false) => "ge",
+        (BinaryOperator::Less, true) => "b",
+        (BinaryOperator::LessEqual, true) => "be",
+        (BinaryOperator::Greater, true) => "a",
+        (BinaryOperator::GreaterEqual, true) => "ae",
+        _ => panic!(), // never happens, not a comparison
+    }
+}
+
+/// Suffix of the SSE instructions for a float type, "ss" for the single
+/// precision and "sd" for the double precision
+fn sse_suffix(type_: &Type) -> &'static str {
+    match *type_ {
+        Type::F32 => "ss",
+        _ => "sd",
+    }
 }
 
 impl Caller for LinuxCompiler {}
@@ -123,20 +1391,77 @@ impl Compiler for LinuxCompiler {
     }
 
     fn finish_one(&mut self, source: &String) {
-        // Write all static data
+        self.define_frame_size();
+
+        if self.uses_runtime_error {
+            // The message is given by "rsi" and its length by "rdx"
+            self.data().asm_formatter.add_instructions(&mut vec![
+                i!(label!(&RUNTIME_ERROR.to_string())),
+                i!(Mov, reg!(Rax), Op::Literal(1)),
+                i!(Mov, reg!(Rdi), Op::Literal(2)), // stderr
+                i!(Syscall),
+                i!(Mov, reg!(Rax), Op::Literal(60)),
+                i!(Mov, reg!(Rdi), Op::Literal(1)),
+                i!(Syscall),
+            ]);
+        }
+
+        if self.uses_print {
+            for line in print_routines().lines() {
+                let instruction = match line.strip_suffix(':') {
+                    Some(routine) if !routine.starts_with('.') => i!(label!(routine)),
+                    _ => i!(Expression(line.trim().to_string())),
+                };
+                self.data().asm_formatter.add_instruction(instruction);
+            }
+
+            for value in ["true", "false"] {
+                self.add_rodata(&format!("{}_{}", PRINT_BOOL, value), value);
+            }
+        }
+
+        // Write all static data, the empty sections are not written
+        if !self.section_rodata.is_empty() {
+            self.data().asm_formatter.add_instruction(
+                i!(Expression("section .rodata".to_string()))
+            );
+            let mut section_rodata = self.section_rodata.clone();
+            self.data().asm_formatter.add_instructions(&mut section_rodata);
+        }
+
         self.data().asm_formatter.add_instruction(i!(section!(Data)));
         
         let mut section_data = self.section_data.clone();
         self.data().asm_formatter.add_instructions(&mut section_data);
 
+        if !self.section_bss.is_empty() {
+            self.data().asm_formatter.add_instruction(
+                i!(Expression("section .bss".to_string()))
+            );
+            let mut section_bss = self.section_bss.clone();
+            self.data().asm_formatter.add_instructions(&mut section_bss);
+        }
+
         // Reset for the next file
+        self.uses_runtime_error = false;
+        self.uses_print = false;
         self.section_data = vec![];
+        self.section_rodata = vec![];
+        self.section_bss = vec![];
+        self.strings.clear();
+        self.data().externs = vec![];
 
         // Write assembly
         {
             let current_source = self.data().current_source.clone();
             let path = Path::new(&current_source);
             self.data().asm_formatter.to_file(&path);
+
+            // The local variables are commented with their types, even the
+            // inferred ones
+            if self.data().emit {
+                print!("; {}\n{}", current_source, fs::read_to_string(path).unwrap());
+            }
         }
         self.data().asm_formatter.reset();
 
@@ -171,87 +1496,653 @@ impl Compiler for LinuxCompiler {
         self.change_variable_value(&variable);
     }
 
-    fn add_static_variable(&mut self, variable: Variable) {
-        let mut init_value: String = variable.current_value().clone();
+    fn add_static_variable(&mut self, variable: Variable, is_initialized: bool) {
+        // Visible from the other object files, the directive can be written
+        // in any section
+        self.section_data.push(
+            i!(Global, Op::Label(variable.id().to_string()))
+        );
 
-        // Auto terminate strings by NULL character
-        if *variable.type_() == Type::Str && init_value != "0".to_string() {
-            init_value = format!("`{}`", &init_value[1..init_value.len() - 1]);
-            init_value += ", 0";
+        match is_initialized {
+            true => self.add_data(&variable),
+            false => self.add_bss(&variable),
         }
-
-        self.section_data.push(
-            i!(
-                label!(variable.id()), 
-                variable.type_().to_asm_operand(), 
-                Op::Expression(init_value)
-            )
-        )
     }
 
     fn add_function(&mut self, function: Function) {
+        self.define_frame_size();
+
+        // The stack pointer stays under all the variables of the function, the
+        // values pushed while evaluating an expression do not overwrite them
+        let frame_size = self.new_label("frame_size");
         self.data().asm_formatter.add_instructions(&mut vec![
             i!(Global, Op::Label(function.id().to_string())),
             i!(label!(function.id())),
             i!(Push, reg!(Rbp)),
-            i!(Mov, reg!(Rbp), reg!(Rsp))
+            i!(Mov, reg!(Rbp), reg!(Rsp)),
+            i!(Expression("sub".to_string()), reg!(Rsp), Op::Label(frame_size.clone())),
         ]);
+        self.frame_size = Some(frame_size);
 
+        self.data().variable_stack.clear();
         self.data().i_variable_stack = 0;
+        self.data().pushed = 0;
+
+        self.return_address = None;
+        if returns_in_memory(function.return_type()) {
+            let position = self.allocate(8, 8);
+            self.return_address = Some(position);
+            self.data().asm_formatter.add_instruction(
+                i!(Mov, Op::Expression(format!("[rbp-{}]", position)), reg!(Rdi))
+                    .with_comment("returned structure".to_string())
+                    .clone()
+            );
+        }
+
+        let types: Vec<Type> = function.params().iter()
+            .map(| x | x.type_().clone())
+            .collect();
+        let (locations, _) = argument_locations(&types, function.return_type());
+
+        // Copied once all the registers are saved, copying a structure or an
+        // array uses "rdi", "rsi" and "rcx"
+        let mut on_stack: Vec<(usize, usize, Type)> = vec![];
+
+        for (param, location) in function.params().iter().zip(locations) {
+            let type_ = param.type_().clone();
+
+            // A structure passed in registers is stored by whole eightbytes
+            let (size, alignment) = match location {
+                Location::Registers(ref registers) if type_.is_aggregate() => {
+                    (registers.len() * 8, 8)
+                }
+                _ => (type_.to_usize(), type_.alignment()),
+            };
+            let position = self.allocate(size, alignment);
+
+            self.data().variable_stack.insert(
+                param.id().to_string(),
+                Variable::new(param.id().to_string(), type_.clone(), String::new(), position)
+            );
+
+            match location {
+                Location::Registers(registers) => {
+                    for (i, register) in registers.iter().enumerate() {
+                        let (size, operand_size) = match type_.is_aggregate() {
+                            true => (8, "qword"),
+                            false => (type_.to_usize(), type_.operand_size()),
+                        };
+                        let address = Op::Expression(format!(
+                            "{} [rbp-{}]",
+                            operand_size,
+                            position - i * 8
+                        ));
+
+                        self.data().asm_formatter.add_instruction(
+                            match register.starts_with("xmm") {
+                                true if type_.is_float() => i!(
+                                    Expression(format!("mov{}", sse_suffix(&type_))),
+                                    address,
+                                    Op::Expression(register.to_string())
+                                ),
+                                true => i!(
+                                    Expression("movq".to_string()),
+                                    address,
+                                    Op::Expression(register.to_string())
+                                ),
+                                false => i!(
+                                    Mov,
+                                    address,
+                                    Op::Expression(register_part(register, size))
+                                ),
+                            }
+                        );
+                    }
+                }
+                Location::Stack(offset) => on_stack.push((position, offset, type_)),
+            }
+        }
+
+        // Above the return address and the saved "rbp"
+        for (position, offset, type_) in on_stack {
+            let size = type_.to_usize();
+            match type_.is_aggregate() {
+                true => self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(
+                        Expression("lea".to_string()),
+                        reg!(Rsi),
+                        Op::Expression(format!("[rbp+{}]", 16 + offset))
+                    ),
+                    i!(
+                        Expression("lea".to_string()),
+                        reg!(Rdi),
+                        Op::Expression(format!("[rbp-{}]", position))
+                    ),
+                    i!(Mov, reg!(Rcx), Op::Expression(size.to_string())),
+                    i!(Expression("rep".to_string()), Op::Expression("movsb".to_string())),
+                ]),
+                false => self.data().asm_formatter.add_instructions(&mut vec![
+                    i!(Mov, reg!(Rax), Op::Expression(format!("[rbp+{}]", 16 + offset))),
+                    i!(
+                        Mov,
+                        Op::Expression(format!("{} [rbp-{}]", type_.operand_size(), position)),
+                        Op::Expression(rax_part(size).to_string())
+                    ),
+                ]),
+            }
+        }
+    }
+
+    fn call_function(&mut self, function: &Function, args: &Vec<Expression>) {
+        let types: Vec<Type> = function.params().iter()
+            .map(| x | x.type_().clone())
+            .collect();
+        let (locations, stack_size) = argument_locations(&types, function.return_type());
+
+        // The returned structure is kept in a slot of the stack frame, by
+        // whole eightbytes
+        let returned = match function.return_type() {
+            Some(Type::Struct(struct_)) => {
+                let position = self.allocate(align(struct_.size, 8), 8);
+                Some((position, struct_.classes()))
+            }
+            _ => None,
+        };
+
+        // The stack frame is aligned on 16 bytes, the values pushed
+        // under it are padded
+        let already_pushed = self.data().pushed;
+        let stack_size = align(already_pushed + stack_size, 16) - already_pushed;
+        if stack_size != 0 {
+            self.data().pushed += stack_size;
+            self.data().asm_formatter.add_instruction(
+                i!(Expression("sub".to_string()), reg!(Rsp), Op::Expression(stack_size.to_string()))
+            );
+        }
+
+        // The values passed in registers are pushed while the next arguments
+        // are evaluated, then popped in their registers
+        let mut pushed: Vec<&str> = vec![];
+
+        for ((arg, type_), location) in args.iter().zip(types.iter()).zip(locations) {
+            self.evaluate(arg, type_);
+
+            match location {
+                Location::Registers(registers) => {
+                    match (type_.is_aggregate(), type_.is_float()) {
+                        (true, _) => {
+                            for i in 0..registers.len() {
+                                self.push(Op::Expression(format!("qword [rax+{}]", i * 8)));
+                            }
+                        }
+                        (false, true) => {
+                            self.data().asm_formatter.add_instruction(i!(
+                                Expression(match type_ {
+                                    Type::F32 => "movd".to_string(),
+                                    _ => "movq".to_string(),
+                                }),
+                                Op::Expression(rax_part(type_.to_usize()).to_string()),
+                                Op::Expression("xmm0".to_string())
+                            ));
+                            self.push(reg!(Rax));
+                        }
+                        (false, false) => self.push(reg!(Rax)),
+                    }
+                    pushed.extend(registers);
+                }
+                Location::Stack(offset) => {
+                    let address = format!("[rsp+{}]", offset + pushed.len() * 8);
+
+                    let mut instructions = match (type_.is_aggregate(), type_.is_float()) {
+                        (true, _) => vec![
+                            i!(Mov, reg!(Rsi), reg!(Rax)),
+                            i!(Expression("lea".to_string()), reg!(Rdi), Op::Expression(address)),
+                            i!(Mov, reg!(Rcx), Op::Expression(type_.to_usize().to_string())),
+                            i!(Expression("rep".to_string()), Op::Expression("movsb".to_string())),
+                        ],
+                        (false, true) => vec![i!(
+                            Expression(format!("mov{}", sse_suffix(type_))),
+                            Op::Expression(format!("{} {}", type_.operand_size(), address)),
+                            Op::Expression("xmm0".to_string())
+                        )],
+                        (false, false) => vec![i!(
+                            Mov,
+                            Op::Expression(format!("{} {}", type_.operand_size(), address)),
+                            Op::Expression(rax_part(type_.to_usize()).to_string())
+                        )],
+                    };
+                    self.data().asm_formatter.add_instructions(&mut instructions);
+                }
+            }
+        }
+
+        for register in pushed.iter().rev() {
+            match register.starts_with("xmm") {
+                true => {
+                    self.pop(reg!(Rax));
+                    self.data().asm_formatter.add_instruction(i!(
+                        Expression("movq".to_string()),
+                        Op::Expression(register.to_string()),
+                        reg!(Rax)
+                    ));
+                }
+                false => self.pop(Op::Expression(register.to_string())),
+            }
+        }
+
+        if let Some((position, None)) = returned {
+            self.data().asm_formatter.add_instruction(i!(
+                Expression("lea".to_string()),
+                reg!(Rdi),
+                Op::Expression(format!("[rbp-{}]", position))
+            ));
+        }
+
+        // Number of vector registers used, read by the variadic functions
+        let sse_count = pushed.iter().filter(| x | x.starts_with("xmm")).count();
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Mov, Op::Expression("eax".to_string()), Op::Expression(sse_count.to_string())),
+            i!(Expression("call".to_string()), Op::Label(function.id().to_string())),
+        ]);
+
+        // The address of the structure is given in "rax", like for a variable
+        if let Some((position, Some(classes))) = returned {
+            for (i, register) in return_registers(&classes).iter().enumerate() {
+                let address = Op::Expression(format!("qword [rbp-{}]", position - i * 8));
+                self.data().asm_formatter.add_instruction(
+                    match register.starts_with("xmm") {
+                        true => i!(
+                            Expression("movq".to_string()),
+                            address,
+                            Op::Expression(register.to_string())
+                        ),
+                        false => i!(Mov, address, Op::Expression(register.to_string())),
+                    }
+                );
+            }
+            self.data().asm_formatter.add_instruction(i!(
+                Expression("lea".to_string()),
+                reg!(Rax),
+                Op::Expression(format!("[rbp-{}]", position))
+            ));
+        }
+
+        if stack_size != 0 {
+            self.data().pushed -= stack_size;
+            self.data().asm_formatter.add_instruction(
+                i!(Expression("add".to_string()), reg!(Rsp), Op::Expression(stack_size.to_string()))
+            );
+        }
     }
 
     fn change_variable_value(&mut self, variable: &Variable) {
-        let i_variable_stack = self.data().i_variable_stack;
+        let type_ = variable.type_().clone();
+        if type_.is_aggregate() {
+            self.initialize_aggregate(variable);
+            return;
+        }
+
+        // The characters are copied from the read-only string
+        if type_ == Type::Str {
+            let text = match Expression::parse(variable.current_value()) {
+                Ok(Expression::Literal(Literal::Str(text))) => text,
+                _ => panic!(), // never happens, checked by "types"
+            };
+            let text_id = self.intern(&text);
+
+            self.data().asm_formatter.add_instructions(&mut vec![
+                i!(Mov, reg!(Rsi), Op::Label(text_id)),
+                i!(
+                    Expression("lea".to_string()),
+                    reg!(Rdi),
+                    Op::Expression(format!("[rbp-{}]", variable.stack_pos()))
+                )
+                .with_comment(format!("{}: {}", variable.id(), variable.type_()))
+                .clone(),
+                i!(Mov, reg!(Rcx), Op::Expression(string_size(&text).to_string())),
+                i!(Expression("rep".to_string()), Op::Expression("movsb".to_string())),
+            ]);
+            return;
+        }
+
+        let address = Op::Expression(format!(
+            "{} [rbp-{}]",
+            type_.operand_size(),
+            variable.stack_pos()
+        ));
+
+        let value = Expression::parse(variable.current_value());
+        let constant = match value {
+            Ok(ref expression) => expression.constant_value_as(Some(&type_), &self.data).ok(),
+            Err(_) => None,
+        };
+
+        let mut instruction = match (value, constant) {
+            // Computed at compile time, an immediate operand is at most 32
+            // bits
+            (_, Some(constant)) if fits(constant, &Type::I32) => {
+                i!(Mov, address, Op::Expression(constant.to_string()))
+            }
+            (Err(_), _) => {
+                i!(Mov, address, Op::Expression(variable.current_value().to_string()))
+            }
+            // Computed at runtime
+            (Ok(expression), _) => {
+                self.evaluate(&expression, &type_);
+
+                match type_.is_float() {
+                    true => i!(
+                        Expression(format!("mov{}", sse_suffix(&type_))),
+                        address,
+                        Op::Expression("xmm0".to_string())
+                    ),
+                    false => i!(
+                        Mov,
+                        address,
+                        Op::Expression(rax_part(type_.to_usize()).to_string())
+                    ),
+                }
+            }
+        };
 
         self.data().asm_formatter.add_instruction(
-            i!(
-                Mov, 
-                Op::Expression(format!("[rbp-{}]", i_variable_stack)),
-                Op::Dword, 
-                Op::Expression(variable.current_value().to_string())
-            )
-            .with_comment(variable.id().to_string())
-            .clone()
+            instruction
+                .with_comment(format!("{}: {}", variable.id(), variable.type_()))
+                .clone()
         );
     }
 
-    fn return_(&mut self, value: String) {
+    fn assign(&mut self, target: &Expression, value: &Expression) {
+        let type_ = self.evaluate_address(target);
+        self.push(reg!(Rax));
+        self.store(value, &type_);
+    }
+
+    fn evaluate(&mut self, expression: &Expression, type_: &Type) {
+        match expression {
+            Expression::Literal(Literal::Integer(value)) => {
+                self.data().asm_formatter.add_instruction(
+                    i!(Mov, reg!(Rax), Op::Expression(value.to_string()))
+                );
+            }
+            Expression::Literal(Literal::Float(value)) => self.load_float(*value, type_),
+            Expression::Literal(Literal::Bool(value)) => {
+                self.data().asm_formatter.add_instruction(
+                    i!(Mov, reg!(Rax), Op::Literal(if *value { 1 } else { 0 }))
+                );
+            }
+            Expression::Literal(Literal::Str(_)) => panic!(), // never happens, strings are data
+            // The constants and the variants of the enums have no storage
+            Expression::Identifier(_) | Expression::Field(_, _)
+                if expression.is_constant_name(&self.data) =>
+            {
+                let value = expression.constant_value(&self.data).unwrap();
+                self.data().asm_formatter.add_instruction(
+                    i!(Mov, reg!(Rax), Op::Expression(value.to_string()))
+                        .with_comment(expression.to_string())
+                        .clone()
+                );
+            }
+            Expression::Identifier(id) => self.load(id),
+            Expression::Index(_, _)
+            | Expression::Field(_, _)
+            | Expression::Unary(UnaryOperator::Dereference, _) => {
+                let type_ = self.evaluate_address(expression);
+                if !type_.is_aggregate() {
+                    self.load_from("[rax]".to_string(), &type_, expression.to_string());
+                }
+            }
+            Expression::Len(operand) => {
+                let count = Expression::len_of(operand, &self.data)
+                    .unwrap(); // checked by "types"
+                self.data().asm_formatter.add_instruction(
+                    i!(Mov, reg!(Rax), Op::Expression(count.to_string()))
+                );
+            }
+            // Never happens, they are stored by `Self::store()`
+            Expression::Array(_) | Expression::Repeat(_, _) | Expression::Struct(_, _) => {
+                panic!()
+            }
+
+            Expression::Unary(UnaryOperator::Not, operand) => {
+                self.evaluate(operand, &Type::Bool);
+                self.data().asm_formatter.add_instruction(
+                    i!(Xor, reg!(Rax), Op::Literal(1))
+                );
+            }
+            Expression::Unary(UnaryOperator::Negate, operand) => {
+                self.evaluate(operand, type_);
+
+                // The sign bit of a float is inverted
+                let xmm0 = Op::Expression("xmm0".to_string());
+                let xmm1 = Op::Expression("xmm1".to_string());
+                let mut instructions = match *type_ {
+                    Type::F32 => vec![
+                        i!(
+                            Mov,
+                            Op::Expression("eax".to_string()),
+                            Op::Expression("0x80000000".to_string())
+                        ),
+                        i!(
+                            Expression("movd".to_string()),
+                            xmm1.clone(),
+                            Op::Expression("eax".to_string())
+                        ),
+                        i!(Expression("xorps".to_string()), xmm0, xmm1),
+                    ],
+                    Type::F64 => vec![
+                        i!(Mov, reg!(Rax), Op::Expression("0x8000000000000000".to_string())),
+                        i!(Expression("movq".to_string()), xmm1.clone(), reg!(Rax)),
+                        i!(Expression("xorpd".to_string()), xmm0, xmm1),
+                    ],
+                    _ => vec![i!(Expression("neg".to_string()), reg!(Rax))],
+                };
+                self.data().asm_formatter.add_instructions(&mut instructions);
+                if type_.is_integer() {
+                    self.extend(type_);
+                }
+            }
+            Expression::Unary(UnaryOperator::BitNot, operand) => {
+                self.evaluate(operand, type_);
+                self.data().asm_formatter.add_instruction(
+                    i!(Expression("not".to_string()), reg!(Rax))
+                );
+                self.extend(type_);
+            }
+            Expression::Unary(UnaryOperator::AddressOf, operand) => {
+                self.evaluate_address(operand);
+            }
+            Expression::Binary(operator, lhs, rhs) if operator.is_logical() => {
+                self.evaluate_logical(operator, lhs, rhs);
+            }
+            Expression::Binary(operator, lhs, rhs) if operator.is_arithmetic() => {
+                match Expression::pointer_arithmetic_type(operator, lhs, rhs, &self.data) {
+                    Ok(Some(_)) => self.evaluate_pointer_arithmetic(operator, lhs, rhs),
+                    _ => self.evaluate_arithmetic(operator, lhs, rhs, type_),
+                }
+            }
+            Expression::Binary(operator, lhs, rhs) if operator.is_bitwise() => {
+                self.evaluate_bitwise(operator, lhs, rhs, type_);
+            }
+            Expression::Binary(operator, lhs, rhs) => {
+                self.evaluate_comparison(operator, lhs, rhs);
+            }
+            // The called function extends its returned value to "rax", SEE
+            // `Self::return_()`
+            Expression::Call(name, args) => {
+                let function = self.callee(name);
+                self.call_function(&function, args);
+            }
+        }
+    }
+
+    fn return_(&mut self, value: Option<&Expression>, type_: &Option<Type>) {
+        // The evaluation extends the value to "rax", so the caller reads it
+        // in the part of the width of the type, like "eax" or "al"
+        match (value, type_) {
+            (_, Some(Type::Struct(struct_))) => self.return_struct(value, struct_),
+            (Some(value), Some(type_)) => self.evaluate(value, type_),
+            (None, Some(type_)) if type_.is_float() => {
+                self.data().asm_formatter.add_instruction(i!(
+                    Expression("xorps".to_string()),
+                    Op::Expression("xmm0".to_string()),
+                    Op::Expression("xmm0".to_string())
+                ));
+            }
+            (None, Some(_)) => {
+                self.data().asm_formatter.add_instruction(i!(Xor, reg!(Rax), reg!(Rax)));
+            }
+            _ => {}
+        }
+
         self.data().asm_formatter.add_instructions(&mut vec![
-            i!(Mov, reg!(Rax), Op::Expression(value)),
             i!(Mov, reg!(Rsp), reg!(Rbp)),
             i!(Pop, reg!(Rbp)),
             i!(Ret),
         ]);
     }
 
-    fn print(&mut self, to_print: String) {
-        let to_print_id = format!("_string_");
+    fn jump_if_equal(&mut self, value: i128, label: &String) {
+        // "cmp" only takes a 32 bits immediate
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Mov, reg!(Rcx), Op::Expression(value.to_string())),
+            i!(Expression("cmp".to_string()), reg!(Rax), reg!(Rcx)),
+            i!(Expression("je".to_string()), Op::Label(label.clone())),
+        ]);
+    }
+
+    /// A comparison is directly followed by the jump with the opposite
+    /// condition, without placing its result in "rax"
+    fn jump_if_false(&mut self, condition: &Expression, label: &String) {
+        if let Expression::Binary(operator, lhs, rhs) = condition {
+            let type_ = match operator.is_comparison() {
+                true => Expression::operands_type(lhs, rhs, &self.data),
+                false => None,
+            };
 
-        self.add_static_variable(
-            Variable::static_(
-                to_print_id.clone(),
-                Type::Str,
-                to_print
-            )
-        );
+            // A NaN float needs the parity flag for "==" and "!=", they are
+            // evaluated in "rax" instead
+            if let Some(type_) = type_.filter(|type_| !type_.is_float()
+                || !matches!(operator, BinaryOperator::Equal | BinaryOperator::NotEqual))
+            {
+                self.evaluate_operands(lhs, rhs, &type_);
+                let condition = self.compare(operator, &type_);
+                let opposite = condition_code(&condition.opposite(), !type_.is_signed());
 
+                self.data().asm_formatter.add_instruction(
+                    i!(Expression(format!("j{}", opposite)), Op::Label(label.clone()))
+                );
+                return;
+            }
+        }
+
+        self.evaluate(condition, &Type::Bool);
         self.data().asm_formatter.add_instructions(&mut vec![
-            i!(Mov, reg!(Rdi), Op::Label(to_print_id.clone())),
-            i!(Xor, reg!(Rcx), reg!(Rcx)),
-            i!(Not, reg!(Rcx)),
-            i!(Xor, reg!(Al), reg!(Al)),
-            i!(Expression("cld".to_string())),
-            i!(Expression("repnz".to_string()), Op::Expression("scasb".to_string())),
-            i!(Not, reg!(Rcx)),
-            i!(Expression("dec".to_string()), reg!(Rcx)),
-            i!(Mov, reg!(Rdx), reg!(Rcx)),
-            i!(Mov, reg!(Rsi), Op::Label(to_print_id.clone())),
-            i!(Mov, reg!(Rax), Op::Literal(1)),
-            i!(Mov, reg!(Rdi), reg!(Rax)),
-            i!(Syscall)
+            i!(Expression("test".to_string()), reg!(Rax), reg!(Rax)),
+            i!(Expression("jz".to_string()), Op::Label(label.clone())),
+        ]);
+    }
+
+    fn jump_if_out_of_range(&mut self, counter: &Counter, label: &String) {
+        self.load_counter(counter);
+
+        // The start is after the end, or before it for a descending range
+        let operator = match (counter.step > 0, counter.is_inclusive) {
+            (true, false) => BinaryOperator::GreaterEqual,
+            (true, true) => BinaryOperator::Greater,
+            (false, false) => BinaryOperator::LessEqual,
+            (false, true) => BinaryOperator::Less,
+        };
+        let unsigned = !counter.variable.type_().is_signed();
+
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Expression("cmp".to_string()), reg!(Rax), reg!(Rcx)),
+            i!(
+                Expression(format!("j{}", condition_code(&operator, unsigned))),
+                Op::Label(label.clone())
+            ),
         ]);
     }
 
+    fn step_counter(&mut self, counter: &Counter, label: &String) {
+        self.load_counter(counter);
+
+        // The distance between the counter and the end is placed in "rcx", it
+        // fits in 64 bits when it's read as an unsigned integer
+        let (mut distance, mnemonic) = match counter.step > 0 {
+            true => (
+                vec![i!(Expression("sub".to_string()), reg!(Rcx), reg!(Rax))],
+                "add"
+            ),
+            false => (
+                vec![
+                    i!(Mov, reg!(Rdx), reg!(Rcx)),
+                    i!(Mov, reg!(Rcx), reg!(Rax)),
+                    i!(Expression("sub".to_string()), reg!(Rcx), reg!(Rdx)),
+                ],
+                "sub"
+            ),
+        };
+        self.data().asm_formatter.add_instructions(&mut distance);
+
+        // The end is reached when the distance is less than the step, or
+        // equal to it when the end is excluded
+        let exit = match counter.is_inclusive {
+            true => "jb",
+            false => "jbe",
+        };
+
+        let variable = counter.variable.clone();
+        let type_ = variable.type_();
+        self.data().asm_formatter.add_instructions(&mut vec![
+            i!(Mov, reg!(Rdx), Op::Expression(counter.step.abs().to_string())),
+            i!(Expression("cmp".to_string()), reg!(Rcx), reg!(Rdx)),
+            i!(Expression(exit.to_string()), Op::Label(label.clone())),
+            i!(Expression(mnemonic.to_string()), reg!(Rax), reg!(Rdx)),
+            i!(
+                Mov,
+                Op::Expression(format!(
+                    "{} [rbp-{}]",
+                    type_.operand_size(),
+                    variable.stack_pos()
+                )),
+                Op::Expression(rax_part(type_.to_usize()).to_string())
+            ),
+        ]);
+    }
+
+    fn jump(&mut self, label: &String) {
+        self.data().asm_formatter.add_instruction(
+            i!(Expression("jmp".to_string()), Op::Label(label.clone()))
+        );
+    }
+
+    fn add_label(&mut self, label: &String) {
+        self.data().asm_formatter.add_instruction(i!(label!(label)));
+    }
+
+    fn add_assembly_code(&mut self, code: String) {
+        self.data().asm_formatter.add_instruction(i!(Expression(code)));
+    }
+
+    fn print(&mut self, format: &Format) {
+        self.uses_print = true;
+
+        for piece in format.pieces.iter() {
+            match piece {
+                // The same text printed many times is defined once
+                Piece::Text(text) => {
+                    let text_id = self.intern(text);
+                    self.data().asm_formatter.add_instructions(&mut vec![
+                        i!(Mov, reg!(Rax), Op::Label(text_id)),
+                        i!(Expression("call".to_string()), Op::Label(PRINT_STRING.to_string())),
+                    ]);
+                }
+                Piece::Value(value, radix) => self.print_value(value, *radix),
+            }
+        }
+    }
+
     fn exit(&mut self, value: String) {
         self.data().asm_formatter.add_instructions(&mut vec![
             i!(Mov, reg!(Rax), Op::Literal(60)),
@@ -4,13 +4,16 @@
 
 use std::string::ToString;
 
+use checking::identifier;
+
 use crate::defaults;
 
 /// Simple structure to manage a scope, an indicator to where we are in the 
 /// junon program \
 /// Implements a `to_string()` function to create a string with all sub scopes
 /// separated by the defaults scope separator, that is why a simple Vec is not
-/// used.
+/// used. \
+/// The non-ASCII characters are mangled, the string is an assembler symbol
 #[derive(Clone)]
 pub struct Scope {
     scope: Vec<String>,
@@ -25,7 +28,7 @@ impl ToString for Scope {
                 to_write += defaults::SCOPE_SEPARATOR
             }
         }
-        to_write 
+        identifier::mangle(&to_write)
     }
 }
 
@@ -50,6 +53,14 @@ impl Scope {
         self.scope.pop();
     }
 
+    /// The first sub scope, it's the module name of the source file
+    pub fn module(&self) -> String {
+        match self.scope.first() {
+            Some(module) => module.clone(),
+            None => String::new(),
+        }
+    }
+
     pub fn reset(&mut self) {
         self.scope = vec![];
     }
@@ -0,0 +1,320 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use std::collections::HashMap as Dict;
+
+use jup::tokens::Token;
+
+use checking::{
+    data::ProjectData,
+    entry,
+    source::{self, Line},
+    symbols::{Symbol, SymbolKind, SymbolTable},
+    types,
+};
+
+use objects::{
+    constant::Constant,
+    enumeration::Enum,
+    function::Signature,
+    structure::Struct,
+    type_::Type,
+    variable::{Definition, Variable},
+};
+
+use crate::{
+    defaults,
+    scope::Scope,
+};
+
+/// A structure as written in the source code, the types of its fields are
+/// not resolved
+struct Declaration {
+    symbol: Symbol,
+    name: String,
+    fields: Vec<(String, String)>,
+}
+
+/// Build the table of all the functions, static variables, structures, enums
+/// and constants defined in the project, with their scoped names
+pub fn build_table(project: &ProjectData) -> SymbolTable {
+    let mut table = SymbolTable::new();
+    for symbol in enum_symbols(project) {
+        table.add(symbol);
+    }
+    for symbol in const_symbols(project, &table) {
+        table.add(symbol);
+    }
+    for symbol in struct_symbols(project, &table) {
+        table.add(symbol);
+    }
+
+    for source in project.sources.iter() {
+        let module = source.module();
+
+        for (line_i, line) in source.parsed.iter().enumerate() {
+            let id = match (line.first(), line.get(1)) {
+                (Some(Token::Function), _) => {
+                    source::function_id(line, &source.lines[line_i]).unwrap_or_default()
+                }
+                (_, Some(Token::Other(id))) => id.clone(),
+                _ => continue,
+            };
+
+            let mut scope = Scope::from(vec![module.clone()]);
+            let kind = match line.first() {
+                Some(Token::Function) => {
+                    // SEE `Caller::when_function()`
+                    if id == defaults::ENTRY_POINT {
+                        scope.reset();
+                    }
+                    scope.push(id);
+
+                    let signature = match Signature::parse(source.lines[line_i].rest()) {
+                        Ok(signature) => signature,
+                        Err(_) => continue, // reported by "syntax"
+                    };
+                    let context = types::Scope {
+                        locals: &Dict::new(),
+                        symbols: &table,
+                        module: &module,
+                    };
+                    let mut function = signature.function(scope.to_string(), &context);
+                    function.set_return_type(
+                        entry::return_type(&signature.id, function.return_type().clone())
+                    );
+                    SymbolKind::Function(function)
+                }
+                Some(Token::Static) => {
+                    scope.push(id);
+
+                    let (type_, value) = static_info(&source.lines[line_i], &table, &module);
+                    SymbolKind::Static(Variable::static_(scope.to_string(), type_, value))
+                }
+                _ => continue,
+            };
+
+            table.add(Symbol {
+                id: scope.to_string(),
+                module: module.clone(),
+                source: source.path.clone(),
+                line_i,
+                kind,
+            });
+        }
+    }
+    table
+}
+
+/// Type and value of a static variable from its definition line, the
+/// structures, enums and constants are already in the table
+fn static_info(line: &Line, table: &SymbolTable, module: &str) -> (Type, String) {
+    let definition = Definition::parse(line.rest());
+
+    let context = types::Scope {
+        locals: &Dict::new(),
+        symbols: table,
+        module,
+    };
+    let type_ = match definition.type_ {
+        Some(type_as_string) => Type::from_string(type_as_string).resolve(&context),
+        None => Type::NotNative(String::new()),
+    };
+    (type_, definition.value.unwrap_or("0".to_string()))
+}
+
+/// Symbols of the valid enums declared in the project, the other ones are
+/// reported by "syntax"
+fn enum_symbols(project: &ProjectData) -> Vec<Symbol> {
+    let mut symbols = vec![];
+
+    for source in project.sources.iter() {
+        let module = source.module();
+
+        for (line_i, line) in source.parsed.iter().enumerate() {
+            if !source::is_header(line, source::ENUM) {
+                continue;
+            }
+            let name = match line.get(1) {
+                Some(Token::Other(name)) => name.clone(),
+                _ => continue,
+            };
+
+            let mut scope = Scope::from(vec![module.clone()]);
+            scope.push(name);
+
+            if let Ok(enum_) = Enum::parse(scope.to_string(), source.lines[line_i].rest()) {
+                symbols.push(Symbol {
+                    id: scope.to_string(),
+                    module: module.clone(),
+                    source: source.path.clone(),
+                    line_i,
+                    kind: SymbolKind::Enum(enum_),
+                });
+            }
+        }
+    }
+    symbols
+}
+
+/// Symbols of the constants declared in the project, the symbols of `known`
+/// can be used as their types \
+/// A constant is computed once the constants it uses are computed, so the
+/// values are computed again until nothing changes. The remaining ones are
+/// not constant expressions, overflow or depend on themselves
+fn const_symbols(project: &ProjectData, known: &SymbolTable) -> Vec<Symbol> {
+    let mut symbols: Vec<Symbol> = vec![];
+
+    for source in project.sources.iter() {
+        let module = source.module();
+
+        for (line_i, line) in source.parsed.iter().enumerate() {
+            if !source::is_header(line, source::CONST) {
+                continue;
+            }
+            let definition = Definition::parse(source.lines[line_i].rest());
+
+            let mut scope = Scope::from(vec![module.clone()]);
+            scope.push(definition.id.clone());
+
+            let context = types::Scope {
+                locals: &Dict::new(),
+                symbols: known,
+                module: &module,
+            };
+            symbols.push(Symbol {
+                id: scope.to_string(),
+                module: module.clone(),
+                source: source.path.clone(),
+                line_i,
+                kind: SymbolKind::Const(Constant {
+                    id: scope.to_string(),
+                    type_: Type::from_string(definition.type_.unwrap_or_default())
+                        .resolve(&context),
+                    expression: definition.value.unwrap_or_default(),
+                    value: None,
+                }),
+            });
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        let mut table = known.clone();
+        for symbol in symbols.iter() {
+            table.add(symbol.clone());
+        }
+
+        for symbol in symbols.iter_mut() {
+            let constant = match symbol.kind {
+                SymbolKind::Const(ref mut constant) if constant.value.is_none() => constant,
+                _ => continue,
+            };
+
+            let context = types::Scope {
+                locals: &Dict::new(),
+                symbols: &table,
+                module: &symbol.module,
+            };
+            if let Ok(value) = constant.compute(&context) {
+                constant.value = Some(value);
+                changed = true;
+            }
+        }
+    }
+    symbols
+}
+
+/// Symbols of the structures declared in the project, the symbols of `known`
+/// can be used as types of their fields \
+/// A structure is laid out once the types of all its fields are known, so
+/// the declarations are resolved again until nothing changes, the pointed
+/// structures are then given by their scoped names. The remaining ones have a
+/// field of an unknown type or contain themselves
+fn struct_symbols(project: &ProjectData, known: &SymbolTable) -> Vec<Symbol> {
+    let mut declarations: Vec<Declaration> = vec![];
+
+    for source in project.sources.iter() {
+        let module = source.module();
+
+        for (line_i, line) in source.parsed.iter().enumerate() {
+            if !source::is_header(line, source::STRUCT) {
+                continue;
+            }
+            let name = match line.get(1) {
+                Some(Token::Other(name)) => name.clone(),
+                _ => continue,
+            };
+
+            let mut scope = Scope::from(vec![module.clone()]);
+            scope.push(name.clone());
+
+            let fields = source::block_statements(&source.parsed, &source.lines, line_i)
+                .iter()
+                .map(| x | Definition::parse(&source.lines[*x].text))
+                .map(| x | (x.id, x.type_.unwrap_or_default()))
+                .collect();
+
+            declarations.push(Declaration {
+                symbol: Symbol {
+                    id: scope.to_string(),
+                    module: module.clone(),
+                    source: source.path.clone(),
+                    line_i,
+                    kind: SymbolKind::Struct(None),
+                },
+                name,
+                fields,
+            });
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        let mut table = known.clone();
+        for declaration in declarations.iter() {
+            table.add(declaration.symbol.clone());
+        }
+
+        for declaration in declarations.iter_mut() {
+            let context = types::Scope {
+                locals: &Dict::new(),
+                symbols: &table,
+                module: &declaration.symbol.module,
+            };
+            let fields: Vec<(String, Type)> = declaration.fields.iter()
+                .map(| (id, type_) | (
+                    id.clone(),
+                    Type::from_string(type_.clone()).resolve(&context)
+                ))
+                .collect();
+
+            if fields.iter().any(| (_, type_) | type_.unknown().is_some()) {
+                continue;
+            }
+
+            let struct_ = Some(Struct::new(
+                declaration.symbol.id.clone(),
+                declaration.name.clone(),
+                fields,
+            ));
+            if let SymbolKind::Struct(ref current) = declaration.symbol.kind {
+                if *current == struct_ {
+                    continue;
+                }
+            }
+
+            declaration.symbol.kind = SymbolKind::Struct(struct_);
+            changed = true;
+        }
+    }
+
+    declarations.into_iter()
+        .map(| x | x.symbol)
+        .collect()
+}
@@ -87,6 +87,16 @@ impl Logger {
         Ok(value)
     }
 
+    /// Add all the logs of another logger to the log list, keeping their
+    /// order
+    pub fn add_logs(&mut self, other: &Logger) {
+        self.logs.extend(other.logs.iter().cloned());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.logs.is_empty()
+    }
+
     /// When it's useless to return a `Result` structure with
     /// `Self::get_result()`
     pub fn interpret(&self) {
@@ -0,0 +1,32 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::expression::{Context, Expression};
+use crate::type_::Type;
+
+/// Value computed at compile time, declared by "const <id>: <type> = <value>"
+/// \
+/// It has no storage, its uses are replaced by its value
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Constant {
+    /// Scoped name, like "sizes.BUFFER"
+    pub id: String,
+    pub type_: Type,
+    /// The value as written in the source code
+    pub expression: String,
+    /// `None` until it's computed, or when it cannot be : the expression is
+    /// not constant, overflows its type or depends on the constant itself
+    pub value: Option<i128>,
+}
+
+impl Constant {
+    /// Compute the value of the expression, which has to be a value of the
+    /// type of the constant
+    pub fn compute(&self, context: &dyn Context) -> Result<i128, String> {
+        let expression = Expression::parse(&self.expression)?;
+        expression.expect_type(&self.type_, context)?;
+
+        expression.constant_value_as(Some(&self.type_), context)
+    }
+}
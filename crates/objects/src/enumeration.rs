@@ -0,0 +1,101 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::expression::{fits, parse_integer, split_assignment};
+use crate::type_::Type;
+
+/// Enumeration declared in the source code, its variants are integer
+/// constants of its type
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Enum {
+    /// Scoped name, like "colors.Color"
+    pub id: String,
+    /// Name as written in the source code, like "Color"
+    pub name: String,
+    /// Integer type of the values, "int" when it's not written
+    pub type_: Type,
+    /// Names of the variants with their values, in the order of the
+    /// declaration
+    pub variants: Vec<(String, i128)>,
+}
+
+impl Enum {
+    /// Parse a declaration written after "enum" :
+    /// "<id>: <type> = <variant> | <variant> = <value> | ..." \
+    /// A variant without value is the previous one plus one, the first one is
+    /// zero
+    pub fn parse(id: String, text: &str) -> Result<Self, String> {
+        let (declaration, variants) = match split_assignment(text) {
+            Some((declaration, variants)) => (declaration, variants),
+            None => return Err("An enum has variants, written after '='".to_string()),
+        };
+
+        let (name, type_) = match declaration.split_once(':') {
+            Some((name, type_)) => (name.trim(), Type::from_string(type_.trim().to_string())),
+            None => (declaration.trim(), Type::I32),
+        };
+        if !type_.is_integer() {
+            return Err(format!("The type of an enum is an integer type, found '{}'", type_));
+        }
+
+        let mut parsed: Vec<(String, i128)> = vec![];
+        let mut next: i128 = 0;
+
+        for variant in variants.split('|').map(| x | x.trim()) {
+            let (variant, value) = match variant.split_once('=') {
+                Some((variant, value)) => (variant.trim(), Some(value.trim())),
+                None => (variant, None),
+            };
+
+            if variant.is_empty() {
+                return Err("A variant has no name".to_string());
+            }
+            if parsed.iter().any(| (x, _) | x == variant) {
+                return Err(format!("The variant '{}' is declared many times", variant));
+            }
+
+            let value = match value {
+                Some(value) => match value.strip_prefix('-') {
+                    Some(value) => -parse_integer(value.trim())?,
+                    None => parse_integer(value)?,
+                }
+                None => next,
+            };
+
+            if !fits(value, &type_) {
+                return Err(format!(
+                    "The value {} of '{}' is out of the range of '{}'",
+                    value,
+                    variant,
+                    type_
+                ));
+            }
+            if let Some((other, _)) = parsed.iter().find(| (_, x) | *x == value) {
+                return Err(format!(
+                    "The variants '{}' and '{}' have the same value {}",
+                    other,
+                    variant,
+                    value
+                ));
+            }
+
+            parsed.push((variant.to_string(), value));
+            next = value + 1;
+        }
+
+        Ok(Self {
+            id,
+            name: name.to_string(),
+            type_,
+            variants: parsed,
+        })
+    }
+
+    /// Value of the variant named `name`
+    pub fn variant(&self, name: &str) -> Option<i128> {
+        self.variants.iter()
+            .find(| (x, _) | x == name)
+            .map(| (_, value) | *value)
+    }
+}
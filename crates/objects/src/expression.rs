@@ -0,0 +1,1437 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use std::fmt;
+
+use crate::enumeration::Enum;
+use crate::function::Function;
+use crate::structure::Struct;
+use crate::type_::Type;
+
+/// Value written as it is in the source code
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Integer(i128),
+    Float(f64),
+    Bool(bool),
+    /// Content of the string, without its delimiters
+    Str(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Not,
+    /// Opposite of a signed integer or a float : "-x"
+    Negate,
+    /// Inverse of each bit of an integer : "~x"
+    BitNot,
+    /// Address of a variable, an element or a field : "&x"
+    AddressOf,
+    /// Value at the address given by a pointer : "*p"
+    Dereference,
+}
+
+impl UnaryOperator {
+    pub fn symbol(&self) -> &'static str {
+        match *self {
+            Self::Not => "not ",
+            Self::Negate => "-",
+            Self::BitNot => "~",
+            Self::AddressOf => "&",
+            Self::Dereference => "*",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+
+    And,
+    Or,
+}
+
+/// Precedence of the prefix operator "not", its operand can be a comparison
+/// but not a logical operation
+const NOT_PRECEDENCE: u8 = 3;
+
+impl BinaryOperator {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        Some(match symbol {
+            "==" => Self::Equal,
+            "!=" => Self::NotEqual,
+            "<" => Self::Less,
+            "<=" => Self::LessEqual,
+            ">" => Self::Greater,
+            ">=" => Self::GreaterEqual,
+            "+" => Self::Add,
+            "-" => Self::Subtract,
+            "*" => Self::Multiply,
+            "/" => Self::Divide,
+            "%" => Self::Remainder,
+            "&" => Self::BitAnd,
+            "|" => Self::BitOr,
+            "^" => Self::BitXor,
+            "<<" => Self::ShiftLeft,
+            ">>" => Self::ShiftRight,
+            "and" => Self::And,
+            "or" => Self::Or,
+            _ => return None,
+        })
+    }
+
+    /// The operators with the highest precedence are applied first \
+    /// The bitwise operators are applied before the comparisons, so
+    /// "x & 1 == 0" is "(x & 1) == 0"
+    pub fn precedence(&self) -> u8 {
+        match *self {
+            Self::Or => 1,
+            Self::And => 2,
+            Self::Equal
+            | Self::NotEqual
+            | Self::Less
+            | Self::LessEqual
+            | Self::Greater
+            | Self::GreaterEqual => 4,
+            Self::BitOr => 5,
+            Self::BitXor => 6,
+            Self::BitAnd => 7,
+            Self::ShiftLeft | Self::ShiftRight => 8,
+            Self::Add | Self::Subtract => 9,
+            Self::Multiply | Self::Divide | Self::Remainder => 10,
+        }
+    }
+
+    pub fn is_comparison(&self) -> bool {
+        self.precedence() == 4
+    }
+
+    /// Comparison giving the opposite result, "<" gives ">="
+    pub fn opposite(&self) -> Self {
+        match *self {
+            Self::Equal => Self::NotEqual,
+            Self::NotEqual => Self::Equal,
+            Self::Less => Self::GreaterEqual,
+            Self::LessEqual => Self::Greater,
+            Self::Greater => Self::LessEqual,
+            Self::GreaterEqual => Self::Less,
+            _ => panic!(), // never happens, not a comparison
+        }
+    }
+
+    pub fn is_arithmetic(&self) -> bool {
+        self.precedence() >= 9
+    }
+
+    /// "&", "|", "^", "<<" and ">>", only for the integers
+    pub fn is_bitwise(&self) -> bool {
+        (5..=8).contains(&self.precedence())
+    }
+
+    /// The right operand of a shift is a number of bits, it can have another
+    /// integer type than the left one
+    pub fn is_shift(&self) -> bool {
+        *self == Self::ShiftLeft || *self == Self::ShiftRight
+    }
+
+    pub fn is_logical(&self) -> bool {
+        *self == Self::And || *self == Self::Or
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match *self {
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::LessEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterEqual => ">=",
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Remainder => "%",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::And => "and",
+            Self::Or => "or",
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// An expression giving a value, parsed from the source code
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Literal(Literal),
+    Identifier(String),
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+
+    /// Element of an array : "a[i]"
+    Index(Box<Expression>, Box<Expression>),
+    /// List of the elements of an array : "[1, 2, 3]"
+    Array(Vec<Expression>),
+    /// Array with the same value for each element : "[0; 64]"
+    Repeat(Box<Expression>, usize),
+    /// Number of elements of an array, known at compile-time : "len(a)"
+    Len(Box<Expression>),
+
+    /// Field of a structure : "p.x"
+    Field(Box<Expression>, String),
+    /// Value of each field of a structure : "Point { x: 1, y: 2 }"
+    Struct(String, Vec<(String, Expression)>),
+
+    /// Call of a function with its arguments : "add(1, x)" or "lib.add(1, x)"
+    Call(String, Vec<Expression>),
+}
+
+/// Gives the types of the identifiers and the structures, enums and
+/// functions used by an expression
+pub trait Context {
+    fn type_of(&self, identifier: &str) -> Option<Type>;
+    fn struct_(&self, name: &str) -> Option<Struct>;
+    fn enum_(&self, name: &str) -> Option<Enum>;
+    /// Value of the constant named `name`, when it's computed
+    fn constant(&self, name: &str) -> Option<i128>;
+    fn function(&self, name: &str) -> Option<Function>;
+}
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let lexemes = lex(text)?;
+        if lexemes.is_empty() {
+            return Err("An expression was expected".to_string());
+        }
+
+        let mut parser = Parser {
+            lexemes,
+            i: 0,
+        };
+        let expression = parser.parse_expression(0)?;
+
+        match parser.peek() {
+            Some(lexeme) => Err(format!("Unexpected '{}' after the expression", lexeme)),
+            None => Ok(expression),
+        }
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Self::Literal(_))
+    }
+
+    /// If the value is known at compile time when it's used as a value of
+    /// type `type_`, like a literal, a computation with constants or an array
+    /// of literals
+    pub fn is_constant(&self, type_: &Type, context: &dyn Context) -> bool {
+        match (self, type_) {
+            (Self::Literal(_), _) => true,
+            (Self::Array(elements), Type::Array(element, _)) => {
+                elements.iter().all(| x | x.is_constant(element, context))
+            }
+            (Self::Repeat(value, _), Type::Array(element, _)) => {
+                value.is_constant(element, context)
+            }
+            (Self::Struct(_, fields), Type::Struct(struct_)) => fields.iter()
+                .all(| (id, x) | match struct_.field(id) {
+                    Some(field) => x.is_constant(&field.type_, context),
+                    None => false,
+                }),
+            _ => self.constant_value_as(Some(type_), context).is_ok(),
+        }
+    }
+
+    /// If the expression names a constant or a variant of an enum, which
+    /// cannot be assigned
+    pub fn is_constant_name(&self, context: &dyn Context) -> bool {
+        matches!(self, Self::Identifier(_) | Self::Field(_, _))
+            && self.constant_value(context).is_ok()
+    }
+
+    /// Enum and value of the variant given by the expression, like
+    /// "Color.Red"
+    pub fn enum_variant(&self, context: &dyn Context) -> Option<(Enum, i128)> {
+        match self {
+            Self::Field(base, variant) => {
+                let enum_ = context.enum_(&base.qualified_name()?)?;
+                let value = enum_.variant(variant)?;
+                Some((enum_, value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compute the value of an integer, boolean (0 or 1) or enum expression
+    /// made of literals, constants and variants of enums \
+    /// Each operation is checked : its result has to fit in its type
+    pub fn constant_value(&self, context: &dyn Context) -> Result<i128, String> {
+        self.constant_value_as(None, context)
+    }
+
+    /// Same as `Expression::constant_value()` for a value used as a value of
+    /// type `expected`, the operations on literals are computed in this type
+    /// like at runtime
+    pub fn constant_value_as(
+        &self,
+        expected: Option<&Type>,
+        context: &dyn Context
+    ) -> Result<i128, String> {
+        let not_constant = || Err(format!("'{}' is not known at compile time", self));
+
+        let type_ = match expected {
+            Some(expected) if self.can_be(expected, context) => expected.clone(),
+            _ => self.type_(context)?,
+        };
+
+        let value = match self {
+            Self::Literal(Literal::Integer(value)) => *value,
+            Self::Literal(Literal::Bool(value)) => *value as i128,
+            Self::Identifier(id) => match context.constant(id) {
+                Some(value) => value,
+                None => return not_constant(),
+            }
+            // A variant of an enum or a constant of another module
+            Self::Field(_, _) => match self.enum_variant(context) {
+                Some((_, value)) => value,
+                None => match self.qualified_name().and_then(| x | context.constant(&x)) {
+                    Some(value) => value,
+                    None => return not_constant(),
+                }
+            }
+            Self::Len(operand) => match Self::len_of(operand, context) {
+                Some(count) => count as i128,
+                None => return not_constant(),
+            }
+            Self::Unary(UnaryOperator::Not, operand) => {
+                (operand.constant_value(context)? == 0) as i128
+            }
+            Self::Unary(UnaryOperator::Negate, operand) => {
+                -operand.constant_value_as(Some(&type_), context)?
+            }
+            // The bits of an unsigned integer above its size stay cleared
+            Self::Unary(UnaryOperator::BitNot, operand) => {
+                let value = operand.constant_value_as(Some(&type_), context)?;
+                match (type_.is_signed(), type_.integer_range()) {
+                    (false, Some((_, max))) => max - value,
+                    _ => !value,
+                }
+            }
+            Self::Binary(operator, lhs, rhs) => {
+                let operands_type = match operator.is_arithmetic() || operator.is_bitwise() {
+                    true => type_.clone(),
+                    false => Self::operands_type(lhs, rhs, context)?,
+                };
+                let lhs = lhs.constant_value_as(Some(&operands_type), context)?;
+                let rhs = match operator.is_shift() {
+                    true => rhs.constant_value(context)?,
+                    false => rhs.constant_value_as(Some(&operands_type), context)?,
+                };
+
+                let bits = operands_type.to_usize() as i128 * 8;
+                let value = match *operator {
+                    BinaryOperator::Add => lhs.checked_add(rhs),
+                    BinaryOperator::Subtract => lhs.checked_sub(rhs),
+                    BinaryOperator::Multiply => lhs.checked_mul(rhs),
+                    BinaryOperator::Divide | BinaryOperator::Remainder if rhs == 0 => {
+                        return Err(format!("'{}' is a division by zero", self));
+                    }
+                    // Rounded toward zero like "idiv"
+                    BinaryOperator::Divide => lhs.checked_div(rhs),
+                    BinaryOperator::Remainder => lhs.checked_rem(rhs),
+                    BinaryOperator::BitAnd => Some(lhs & rhs),
+                    BinaryOperator::BitOr => Some(lhs | rhs),
+                    BinaryOperator::BitXor => Some(lhs ^ rhs),
+                    BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight
+                        if rhs < 0 || rhs >= bits =>
+                    {
+                        return Err(format!(
+                            "'{}' shifts by {} bits but '{}' has {} bits",
+                            self,
+                            rhs,
+                            operands_type,
+                            bits
+                        ));
+                    }
+                    BinaryOperator::ShiftLeft => Some(lhs << rhs),
+                    // Arithmetic for the signed integers, their sign is kept
+                    BinaryOperator::ShiftRight => Some(lhs >> rhs),
+                    BinaryOperator::Equal => Some((lhs == rhs) as i128),
+                    BinaryOperator::NotEqual => Some((lhs != rhs) as i128),
+                    BinaryOperator::Less => Some((lhs < rhs) as i128),
+                    BinaryOperator::LessEqual => Some((lhs <= rhs) as i128),
+                    BinaryOperator::Greater => Some((lhs > rhs) as i128),
+                    BinaryOperator::GreaterEqual => Some((lhs >= rhs) as i128),
+                    BinaryOperator::And => Some((lhs != 0 && rhs != 0) as i128),
+                    BinaryOperator::Or => Some((lhs != 0 || rhs != 0) as i128),
+                };
+                match value {
+                    Some(value) => value,
+                    None => return Err(format!("'{}' overflows", self)),
+                }
+            }
+            _ => return not_constant(),
+        };
+
+        if type_.is_integer() && !fits(value, &type_) {
+            return Err(format!(
+                "'{}' overflows '{}', it gives {}",
+                self,
+                type_,
+                value
+            ));
+        }
+        Ok(value)
+    }
+
+    /// If a value can be assigned to the expression, like a variable, an
+    /// element of an array, a field of a structure or a pointed value
+    pub fn is_assignable(&self) -> bool {
+        match self {
+            Self::Identifier(_) | Self::Unary(UnaryOperator::Dereference, _) => true,
+            Self::Index(base, _) | Self::Field(base, _) => base.is_assignable(),
+            _ => false,
+        }
+    }
+
+    /// Name made of the identifiers separated by dots, like "lib.value" for
+    /// a static variable of another module
+    pub fn qualified_name(&self) -> Option<String> {
+        match self {
+            Self::Identifier(id) => Some(id.clone()),
+            Self::Field(base, field) => base.qualified_name()
+                .map(| base | format!("{}.{}", base, field)),
+            _ => None,
+        }
+    }
+
+    /// Type of the value given by the expression \
+    /// An integer literal is an "int" (or a "bigint" when it's too big) and a
+    /// float literal is a "f64", unless they are used with or assigned to
+    /// another type of the same kind
+    pub fn type_(&self, context: &dyn Context) -> Result<Type, String> {
+        match self {
+            Self::Literal(Literal::Integer(value)) => {
+                Ok([Type::I32, Type::I64, Type::U64].into_iter()
+                    .find(| x | fits(*value, x))
+                    .unwrap_or(Type::U64))
+            }
+            Self::Literal(Literal::Float(_)) => Ok(Type::F64),
+            Self::Literal(Literal::Bool(_)) => Ok(Type::Bool),
+            Self::Literal(Literal::Str(_)) => Ok(Type::Str),
+
+            Self::Identifier(id) => match context.type_of(id) {
+                Some(type_) => Ok(type_),
+                None => Err(format!("Unknown identifier '{}'", id)),
+            }
+
+            Self::Unary(UnaryOperator::Not, operand) => {
+                operand.expect_type(&Type::Bool, context)?;
+                Ok(Type::Bool)
+            }
+
+            Self::Unary(UnaryOperator::Negate, operand) => {
+                let type_ = operand.type_(context)?;
+                if !type_.is_float() && !(type_.is_integer() && type_.is_signed()) {
+                    return Err(format!(
+                        "'{}' cannot be negated, it's a value of type '{}'",
+                        operand,
+                        type_
+                    ));
+                }
+                Ok(type_)
+            }
+
+            Self::Unary(UnaryOperator::BitNot, operand) => {
+                let type_ = operand.type_(context)?;
+                if !type_.is_integer() {
+                    return Err(format!(
+                        "The operator '~' cannot be used with a value of type '{}'",
+                        type_
+                    ));
+                }
+                Ok(type_)
+            }
+
+            Self::Unary(UnaryOperator::AddressOf, operand) => {
+                if operand.is_constant_name(context) {
+                    return Err(format!("'{}' has no address, it's a constant", operand));
+                }
+                if !operand.is_assignable() {
+                    return Err(format!(
+                        "'{}' has no address, it's not a variable, an element or a field",
+                        operand
+                    ));
+                }
+                Ok(Type::Pointer(Box::new(operand.type_(context)?.by_name(context))))
+            }
+
+            Self::Unary(UnaryOperator::Dereference, operand) => {
+                match operand.type_(context)? {
+                    Type::Pointer(pointee) => {
+                        let pointee = pointee.resolve(context);
+                        match pointee.innermost().unknown() {
+                            Some(name) => Err(format!("Unknown type '{}'", name)),
+                            None => Ok(pointee),
+                        }
+                    }
+                    type_ => Err(format!(
+                        "'{}' is not a pointer but a value of type '{}'",
+                        operand,
+                        type_
+                    )),
+                }
+            }
+
+            Self::Binary(operator, lhs, rhs) if operator.is_logical() => {
+                lhs.expect_type(&Type::Bool, context)?;
+                rhs.expect_type(&Type::Bool, context)?;
+                Ok(Type::Bool)
+            }
+
+            Self::Binary(operator, lhs, rhs) if operator.is_arithmetic() => {
+                if let Some(type_) = Self::pointer_arithmetic_type(operator, lhs, rhs, context)? {
+                    return Ok(type_);
+                }
+                let type_ = Self::operands_type(lhs, rhs, context)?;
+
+                // Checked at runtime otherwise
+                let is_division = *operator == BinaryOperator::Divide
+                    || *operator == BinaryOperator::Remainder;
+                if is_division && **rhs == Self::Literal(Literal::Integer(0)) {
+                    return Err(format!("'{}' is a division by zero", self));
+                }
+
+                let is_number = type_.is_integer()
+                    || (type_.is_float() && *operator != BinaryOperator::Remainder);
+                if !is_number {
+                    return Err(format!(
+                        "The operator '{}' cannot be used with values of type '{}'",
+                        operator,
+                        type_
+                    ));
+                }
+                Ok(type_)
+            }
+
+            Self::Binary(operator, lhs, rhs) if operator.is_bitwise() => {
+                let type_ = match operator.is_shift() {
+                    true => {
+                        let count_type = rhs.type_(context)?;
+                        if !count_type.is_integer() {
+                            return Err(format!(
+                                "A number of bits is an integer, '{}' gives '{}'",
+                                rhs,
+                                count_type
+                            ));
+                        }
+                        lhs.type_(context)?
+                    }
+                    false => Self::operands_type(lhs, rhs, context)?,
+                };
+
+                if !type_.is_integer() {
+                    return Err(format!(
+                        "The operator '{}' cannot be used with values of type '{}'",
+                        operator,
+                        type_
+                    ));
+                }
+                Ok(type_)
+            }
+
+            Self::Binary(operator, lhs, rhs) => {
+                let type_ = Self::operands_type(lhs, rhs, context)?;
+
+                let is_equality = *operator == BinaryOperator::Equal
+                    || *operator == BinaryOperator::NotEqual;
+                let is_number = type_.is_integer() || type_.is_float() || type_.is_pointer();
+                let is_equatable = type_ == Type::Bool || type_.is_enum();
+                if !is_number && !(is_equality && is_equatable) {
+                    return Err(format!(
+                        "The operator '{}' cannot be used with values of type '{}'",
+                        operator,
+                        type_
+                    ));
+                }
+                Ok(Type::Bool)
+            }
+
+            Self::Index(base, index) => {
+                let (element, count) = match base.type_(context)? {
+                    Type::Array(element, count) => (*element, count),
+                    type_ => return Err(format!(
+                        "'{}' is not an array but a value of type '{}'",
+                        base,
+                        type_
+                    )),
+                };
+
+                let index_type = index.type_(context)?;
+                if !index_type.is_integer() {
+                    return Err(format!(
+                        "An index is an integer, '{}' gives '{}'",
+                        index,
+                        index_type
+                    ));
+                }
+
+                // Checked at runtime otherwise
+                if let Self::Literal(Literal::Integer(value)) = **index {
+                    if value < 0 || value >= count as i128 {
+                        return Err(format!(
+                            "The index {} is out of the bounds of '{}' which \
+                            has {} elements",
+                            value,
+                            base,
+                            count
+                        ));
+                    }
+                }
+                Ok(element)
+            }
+
+            Self::Array(elements) => {
+                // A literal takes the type of the other elements
+                let first = match elements.iter().find(| x | !x.is_literal()) {
+                    Some(first) => first,
+                    None => match elements.first() {
+                        Some(first) => first,
+                        None => return Err("An array cannot be empty".to_string()),
+                    }
+                };
+
+                let element = first.type_(context)?;
+                for x in elements.iter() {
+                    x.expect_type(&element, context)?;
+                }
+                Ok(Type::Array(Box::new(element), elements.len()))
+            }
+
+            Self::Repeat(value, count) => {
+                if *count == 0 {
+                    return Err("An array cannot be empty".to_string());
+                }
+                Ok(Type::Array(Box::new(value.type_(context)?), *count))
+            }
+
+            Self::Len(operand) => match operand.type_(context)? {
+                Type::Array(_, count) => Self::Literal(Literal::Integer(count as i128))
+                    .type_(context),
+                type_ => Err(format!(
+                    "'{}' is not an array but a value of type '{}'",
+                    operand,
+                    type_
+                )),
+            }
+
+            Self::Field(base, field) => {
+                // A variant of an enum, like "Color.Red"
+                if let Some(enum_) = base.qualified_name()
+                    .and_then(| name | context.enum_(&name))
+                {
+                    return match enum_.variant(field) {
+                        Some(_) => Ok(Type::Enum(Box::new(enum_))),
+                        None => Err(format!(
+                            "The enum '{}' has no variant '{}'",
+                            enum_.name,
+                            field
+                        )),
+                    };
+                }
+
+                // A field is reached through a pointer to the structure too
+                let struct_ = match base.type_(context) {
+                    Ok(Type::Struct(struct_)) => struct_,
+                    Ok(Type::Pointer(pointee)) => match pointee.resolve(context) {
+                        Type::Struct(struct_) => struct_,
+                        type_ => return Err(format!(
+                            "'{}' is not a pointer to a structure but to a value of type '{}'",
+                            base,
+                            type_
+                        )),
+                    }
+                    Ok(type_) => return Err(format!(
+                        "'{}' is not a structure but a value of type '{}'",
+                        base,
+                        type_
+                    )),
+                    // A static variable of another module
+                    Err(reason) => return match self.qualified_name()
+                        .and_then(| name | context.type_of(&name))
+                    {
+                        Some(type_) => Ok(type_),
+                        None => Err(reason),
+                    },
+                };
+
+                match struct_.field(field) {
+                    Some(field) => Ok(field.type_.clone()),
+                    None => Err(format!(
+                        "The structure '{}' has no field '{}'",
+                        struct_.name,
+                        field
+                    )),
+                }
+            }
+
+            Self::Struct(name, fields) => {
+                let struct_ = match context.struct_(name) {
+                    Some(struct_) => struct_,
+                    None => return Err(format!("Unknown structure '{}'", name)),
+                };
+
+                for (i, (id, value)) in fields.iter().enumerate() {
+                    let field = match struct_.field(id) {
+                        Some(field) => field,
+                        None => return Err(format!(
+                            "The structure '{}' has no field '{}'",
+                            name,
+                            id
+                        )),
+                    };
+                    if fields[..i].iter().any(| (x, _) | x == id) {
+                        return Err(format!("The field '{}' is given many times", id));
+                    }
+                    value.expect_type(&field.type_, context)?;
+                }
+
+                if let Some(missing) = struct_.fields.iter()
+                    .find(| x | !fields.iter().any(| (id, _) | *id == x.id))
+                {
+                    return Err(format!(
+                        "The field '{}' of '{}' is not given",
+                        missing.id,
+                        name
+                    ));
+                }
+                Ok(Type::Struct(Box::new(struct_)))
+            }
+
+            Self::Call(name, args) => {
+                let function = Self::callee(name, args, context)?;
+                match function.return_type() {
+                    Some(type_) => Ok(type_.clone()),
+                    None => Err(format!(
+                        "'{}' gives no value, the function '{}' returns nothing",
+                        self,
+                        name
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Function called by "<name>(<args>)", checked against its signature :
+    /// one argument of the type of each parameter
+    pub fn callee(
+        name: &str,
+        args: &[Expression],
+        context: &dyn Context
+    ) -> Result<Function, String> {
+        let function = match context.function(name) {
+            Some(function) => function,
+            None => return Err(match context.type_of(name) {
+                Some(type_) => format!(
+                    "'{}' is not a function but a value of type '{}'",
+                    name,
+                    type_
+                ),
+                None => format!("Unknown function '{}'", name),
+            }),
+        };
+
+        if args.len() != function.params().len() {
+            return Err(format!(
+                "The function '{}' takes {} arguments but {} are given",
+                name,
+                function.params().len(),
+                args.len()
+            ));
+        }
+
+        for (arg, param) in args.iter().zip(function.params()) {
+            // The arguments are evaluated one by one, a whole array or
+            // structure is passed from its storage
+            if matches!(arg, Self::Array(_) | Self::Repeat(_, _) | Self::Struct(_, _)) {
+                return Err(format!(
+                    "'{}' cannot be passed directly to '{}', store it in a variable first",
+                    arg,
+                    param.id()
+                ));
+            }
+            arg.expect_type(param.type_(), context)?;
+        }
+        Ok(function)
+    }
+
+    /// Type of an arithmetic operation with a pointer, `None` when no operand
+    /// is a pointer \
+    /// An integer is added to or subtracted from a pointer as a number of
+    /// pointed values, and the difference of two pointers of the same type is
+    /// a number of pointed values
+    pub fn pointer_arithmetic_type(
+        operator: &BinaryOperator,
+        lhs: &Expression,
+        rhs: &Expression,
+        context: &dyn Context
+    ) -> Result<Option<Type>, String> {
+        let lhs_type = lhs.type_(context)?;
+        let rhs_type = rhs.type_(context)?;
+
+        match (*operator, &lhs_type, &rhs_type) {
+            (BinaryOperator::Add | BinaryOperator::Subtract, Type::Pointer(_), x)
+                if x.is_integer() => Ok(Some(lhs_type.clone())),
+            (BinaryOperator::Add, x, Type::Pointer(_))
+                if x.is_integer() => Ok(Some(rhs_type.clone())),
+            (BinaryOperator::Subtract, Type::Pointer(_), Type::Pointer(_))
+                if lhs_type == rhs_type => Ok(Some(Type::I64)),
+            _ if lhs_type.is_pointer() || rhs_type.is_pointer() => Err(format!(
+                "The operator '{}' cannot be used with values of type '{}' and '{}'",
+                operator,
+                lhs_type,
+                rhs_type
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Number of elements of the array given by `len(<operand>)`
+    pub fn len_of(operand: &Expression, context: &dyn Context) -> Option<usize> {
+        match operand.type_(context) {
+            Ok(Type::Array(_, count)) => Some(count),
+            _ => None,
+        }
+    }
+
+    /// Common type of the two operands of a binary operation, a number
+    /// literal takes the type of the other operand
+    pub fn operands_type(
+        lhs: &Expression,
+        rhs: &Expression,
+        context: &dyn Context
+    ) -> Result<Type, String> {
+        let lhs_type = lhs.type_(context)?;
+        let rhs_type = rhs.type_(context)?;
+
+        if lhs.can_be(&rhs_type, context) {
+            Ok(rhs_type)
+        } else if rhs.can_be(&lhs_type, context) {
+            Ok(lhs_type)
+        } else {
+            Err(format!(
+                "Mismatched types, '{}' and '{}' cannot be used together",
+                lhs_type,
+                rhs_type
+            ))
+        }
+    }
+
+    /// If the value given by the expression can be used as a value of the
+    /// type `expected`
+    pub fn can_be(&self, expected: &Type, context: &dyn Context) -> bool {
+        match (self, self.type_(context)) {
+            (_, Ok(ref type_)) if type_ == expected => true,
+            (Self::Literal(Literal::Integer(value)), Ok(_)) => fits(*value, expected),
+            (Self::Literal(Literal::Float(_)), Ok(_)) => expected.is_float(),
+            (Self::Len(operand), Ok(_)) => match Self::len_of(operand, context) {
+                Some(count) => fits(count as i128, expected),
+                None => false,
+            }
+            (Self::Array(elements), Ok(_)) => match expected {
+                Type::Array(element, count) => {
+                    *count == elements.len()
+                        && elements.iter().all(| x | x.can_be(element, context))
+                }
+                _ => false,
+            }
+            (Self::Repeat(value, count), Ok(_)) => match expected {
+                Type::Array(element, expected_count) => {
+                    count == expected_count && value.can_be(element, context)
+                }
+                _ => false,
+            }
+            (Self::Binary(operator, lhs, rhs), Ok(_)) if operator.is_shift() => {
+                lhs.can_be(expected, context)
+            }
+            (Self::Binary(operator, lhs, rhs), Ok(_))
+                if operator.is_arithmetic() || operator.is_bitwise() =>
+            {
+                lhs.can_be(expected, context) && rhs.can_be(expected, context)
+            }
+            (Self::Unary(UnaryOperator::Negate, operand), Ok(_)) => {
+                operand.can_be(expected, context)
+                    && (expected.is_float() || expected.is_signed())
+            }
+            (Self::Unary(UnaryOperator::BitNot, operand), Ok(_)) => {
+                operand.can_be(expected, context)
+            }
+            _ => false,
+        }
+    }
+
+    /// Error when the value given by the expression cannot be used as a value
+    /// of the type `expected`
+    pub fn expect_type(&self, expected: &Type, context: &dyn Context) -> Result<(), String> {
+        let type_ = self.type_(context)?;
+        match self.can_be(expected, context) {
+            true => Ok(()),
+            false if type_.is_integer() && expected.is_integer() && self.is_literal() => {
+                Err(format!("'{}' is out of the range of '{}'", self, expected))
+            }
+            false => Err(format!(
+                "Expected a value of type '{}' but '{}' gives '{}'",
+                expected,
+                self,
+                type_
+            )),
+        }
+    }
+
+    /// All the identifiers used by the expression, the names made of
+    /// identifiers separated by dots are given whole, like "lib.value"
+    pub fn identifiers(&self) -> Vec<String> {
+        match self {
+            Self::Literal(_) => vec![],
+            Self::Identifier(id) => vec![id.clone()],
+            Self::Unary(_, operand) => operand.identifiers(),
+            Self::Binary(_, lhs, rhs) | Self::Index(lhs, rhs) => {
+                let mut identifiers = lhs.identifiers();
+                identifiers.extend(rhs.identifiers());
+                identifiers
+            }
+            Self::Array(elements) => elements.iter()
+                .flat_map(| x | x.identifiers())
+                .collect(),
+            Self::Repeat(operand, _) | Self::Len(operand) => operand.identifiers(),
+            Self::Field(base, _) => match self.qualified_name() {
+                Some(name) => vec![name],
+                None => base.identifiers(),
+            }
+            Self::Struct(_, fields) => fields.iter()
+                .flat_map(| (_, x) | x.identifiers())
+                .collect(),
+            // The name of the function is not a value
+            Self::Call(_, args) => args.iter()
+                .flat_map(| x | x.identifiers())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Literal(Literal::Integer(value)) => write!(f, "{}", value),
+            Self::Literal(Literal::Float(value)) => write!(f, "{:?}", value),
+            Self::Literal(Literal::Bool(value)) => write!(f, "{}", value),
+            Self::Literal(Literal::Str(value)) => write!(f, "\"{}\"", value),
+            Self::Identifier(id) => write!(f, "{}", id),
+            Self::Unary(operator, operand) => write!(f, "{}{}", operator.symbol(), operand),
+            Self::Binary(operator, lhs, rhs) => write!(f, "({} {} {})", lhs, operator, rhs),
+            Self::Index(base, index) => write!(f, "{}[{}]", base, index),
+            Self::Array(elements) => write!(
+                f,
+                "[{}]",
+                elements.iter()
+                    .map(| x | x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Repeat(value, count) => write!(f, "[{}; {}]", value, count),
+            Self::Len(operand) => write!(f, "len({})", operand),
+            Self::Field(base, field) => write!(f, "{}.{}", base, field),
+            Self::Struct(name, fields) => write!(
+                f,
+                "{} {{ {} }}",
+                name,
+                fields.iter()
+                    .map(| (id, x) | format!("{}: {}", id, x))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Call(name, args) => write!(
+                f,
+                "{}({})",
+                name,
+                args.iter()
+                    .map(| x | x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// If the integer `value` can be stored in a variable of type `type_`
+pub fn fits(value: i128, type_: &Type) -> bool {
+    match type_.integer_range() {
+        Some((min, max)) => min <= value && value <= max,
+        None => false,
+    }
+}
+
+/// Bytes of the string literal `text` with its ending NULL character, once
+/// its escape sequences are replaced by the assembler \
+/// Example: "a\tb\x41" gives 5
+pub fn string_size(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut size = 1;
+
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '\\' || i + 1 == chars.len() {
+            size += chars[i].len_utf8();
+            i += 1;
+            continue;
+        }
+
+        // Where the digits start, how many of them and in which radix
+        let (start, max, radix) = match chars[i + 1] {
+            'x' => (i + 2, 2, 16),
+            'u' => (i + 2, 4, 16),
+            'U' => (i + 2, 8, 16),
+            '0'..='7' => (i + 1, 3, 8),
+            _ => {
+                size += 1;
+                i += 2;
+                continue;
+            }
+        };
+
+        let mut end = start;
+        while end < chars.len() && end - start < max && chars[end].is_digit(radix) {
+            end += 1;
+        }
+        let digits: String = chars[start..end].iter().collect();
+        let value = u32::from_str_radix(&digits, radix).unwrap_or(0);
+
+        // A code point is written in UTF-8
+        size += match chars[i + 1] {
+            'u' | 'U' => char::from_u32(value).map_or(1, | x | x.len_utf8()),
+            _ => 1,
+        };
+        i = end;
+    }
+    size
+}
+
+/// Split a statement at its assignment sign, the comparison operators are not
+/// assignment signs \
+/// Example: "x: bool = a == b" gives `("x: bool", "a == b")`
+pub fn split_assignment(text: &str) -> Option<(&str, &str)> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut in_string: Option<char> = None;
+
+    for (i, (position, c)) in chars.iter().enumerate() {
+        if let Some(delimiter) = in_string {
+            if *c == delimiter {
+                in_string = None;
+            }
+            continue;
+        }
+
+        match *c {
+            '"' | '\'' => in_string = Some(*c),
+            '=' => {
+                let previous = if i > 0 { chars[i - 1].1 } else { ' ' };
+                let next = match chars.get(i + 1) {
+                    Some((_, next)) => *next,
+                    None => ' ',
+                };
+
+                if next != '=' && !"=<>!".contains(previous) {
+                    return Some((text[..*position].trim(), text[position + 1..].trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Part of an expression
+#[derive(Debug, Clone, PartialEq)]
+enum Lexeme {
+    Integer(i128),
+    Float(f64),
+    Str(String),
+    Word(String),
+    Symbol(String),
+}
+
+impl fmt::Display for Lexeme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lexeme::Integer(value) => write!(f, "{}", value),
+            Lexeme::Float(value) => write!(f, "{:?}", value),
+            Lexeme::Str(value) => write!(f, "\"{}\"", value),
+            Lexeme::Word(word) => write!(f, "{}", word),
+            Lexeme::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+/// Symbols made of many characters, the longest are tried first
+const SYMBOLS: [&str; 6] = ["==", "!=", "<=", ">=", "<<", ">>"];
+
+fn lex(text: &str) -> Result<Vec<Lexeme>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut lexemes = vec![];
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // String
+        if c == '"' || c == '\'' {
+            let start = i + 1;
+            i += 1;
+            while i < chars.len() && (chars[i] != c || chars[i - 1] == '\\') {
+                i += 1;
+            }
+            if i == chars.len() {
+                return Err("The string is never closed".to_string());
+            }
+            lexemes.push(Lexeme::Str(chars[start..i].iter().collect()));
+            i += 1;
+            continue;
+        }
+
+        // Number
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+
+            // Decimal part and exponent of a float
+            let is_float = i + 1 < chars.len()
+                && chars[i] == '.'
+                && chars[i + 1].is_ascii_digit();
+            if is_float {
+                i += 1;
+                while i < chars.len() && (
+                    chars[i].is_ascii_alphanumeric()
+                    || chars[i] == '_'
+                    || ("+-".contains(chars[i]) && "eE".contains(chars[i - 1]))
+                ) {
+                    i += 1;
+                }
+            }
+
+            let number: String = chars[start..i].iter().collect();
+            lexemes.push(match is_float {
+                true => Lexeme::Float(parse_float(&number)?),
+                false => Lexeme::Integer(parse_integer(&number)?),
+            });
+            continue;
+        }
+
+        // Identifier or keyword
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            lexemes.push(Lexeme::Word(chars[start..i].iter().collect()));
+            continue;
+        }
+
+        let rest: String = chars[i..].iter().collect();
+        let symbol = match SYMBOLS.iter().find(| x | rest.starts_with(*x)) {
+            Some(symbol) => symbol.to_string(),
+            None => c.to_string(),
+        };
+        i += symbol.chars().count();
+        lexemes.push(Lexeme::Symbol(symbol));
+    }
+    Ok(lexemes)
+}
+
+/// Parse an integer written in decimal, or in hexadecimal with "0x", or in
+/// binary with "0b", the "_" characters are ignored
+pub fn parse_integer(number: &str) -> Result<i128, String> {
+    let digits: String = number.chars().filter(| c | *c != '_').collect();
+
+    let result = if let Some(hexadecimal) = digits.strip_prefix("0x") {
+        i128::from_str_radix(hexadecimal, 16)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        i128::from_str_radix(binary, 2)
+    } else {
+        digits.parse::<i128>()
+    };
+
+    result.map_err(| _ | format!("'{}' is not a valid number", number))
+}
+
+/// Parse a float written like "1.5", "1_000.0" or "1.5e-3", the "_"
+/// characters are ignored
+pub fn parse_float(number: &str) -> Result<f64, String> {
+    number.chars()
+        .filter(| c | *c != '_')
+        .collect::<String>()
+        .parse::<f64>()
+        .map_err(| _ | format!("'{}' is not a valid number", number))
+}
+
+struct Parser {
+    lexemes: Vec<Lexeme>,
+    i: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Lexeme> {
+        self.lexemes.get(self.i)
+    }
+
+    fn next(&mut self) -> Option<Lexeme> {
+        let lexeme = self.lexemes.get(self.i).cloned();
+        self.i += 1;
+        lexeme
+    }
+
+    /// The binary operator placed at the current position
+    fn peek_operator(&self) -> Option<BinaryOperator> {
+        match self.peek() {
+            Some(Lexeme::Symbol(symbol)) | Some(Lexeme::Word(symbol)) => {
+                BinaryOperator::from_symbol(symbol)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse the operations with operators having a precedence higher than
+    /// `min_precedence`
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Expression, String> {
+        let mut lhs = self.parse_prefix()?;
+
+        while let Some(operator) = self.peek_operator() {
+            if operator.precedence() <= min_precedence {
+                break;
+            }
+            self.next();
+
+            let rhs = self.parse_expression(operator.precedence())?;
+
+            // "a < b < c" has no meaning
+            if operator.is_comparison() {
+                if let Some(next) = self.peek_operator() {
+                    if next.is_comparison() {
+                        return Err(format!(
+                            "The comparison operators cannot be chained, found \
+                            '{}' after '{}'",
+                            next,
+                            operator
+                        ));
+                    }
+                }
+            }
+
+            lhs = Expression::Binary(operator, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// Consume the expected symbol or fail
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        match self.next() {
+            Some(Lexeme::Symbol(ref x)) if x == symbol => Ok(()),
+            Some(lexeme) => Err(format!("Expected '{}' but found '{}'", symbol, lexeme)),
+            None => Err(format!("Expected '{}' at the end of the expression", symbol)),
+        }
+    }
+
+    fn is_next(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Lexeme::Symbol(x)) if x == symbol)
+    }
+
+    /// An operand followed by its indexes, its fields and its arguments, like
+    /// "a[i].x" or "lib.add(1, x)"
+    fn parse_prefix(&mut self) -> Result<Expression, String> {
+        let mut operand = self.parse_operand()?;
+
+        loop {
+            if self.is_next("(") && operand.qualified_name().is_some() {
+                self.next();
+                operand = self.parse_call(operand.qualified_name().unwrap())?;
+            } else if self.is_next("[") {
+                self.next();
+                let index = self.parse_expression(0)?;
+                self.expect("]")?;
+
+                operand = Expression::Index(Box::new(operand), Box::new(index));
+            } else if self.is_next(".") {
+                self.next();
+                let field = match self.next() {
+                    Some(Lexeme::Word(field)) => field,
+                    _ => return Err(format!("A field name was expected after '{}.'", operand)),
+                };
+
+                operand = Expression::Field(Box::new(operand), field);
+            } else {
+                return Ok(operand);
+            }
+        }
+    }
+
+    /// "Point { x: 1, y: 2 }", the name and the opening brace are already
+    /// consumed
+    fn parse_struct(&mut self, name: String) -> Result<Expression, String> {
+        let mut fields = vec![];
+
+        while !self.is_next("}") {
+            if !fields.is_empty() {
+                self.expect(",")?;
+            }
+
+            let id = match self.next() {
+                Some(Lexeme::Word(id)) => id,
+                _ => return Err(format!("A field name was expected in '{}'", name)),
+            };
+            self.expect(":")?;
+            fields.push((id, self.parse_expression(0)?));
+        }
+        self.expect("}")?;
+        Ok(Expression::Struct(name, fields))
+    }
+
+    /// "add(1, x)", the name and the opening parenthesis are already consumed
+    fn parse_call(&mut self, name: String) -> Result<Expression, String> {
+        let mut args = vec![];
+
+        while !self.is_next(")") {
+            if !args.is_empty() {
+                self.expect(",")?;
+            }
+            args.push(self.parse_expression(0)?);
+        }
+        self.expect(")")?;
+        Ok(Expression::Call(name, args))
+    }
+
+    /// "[1, 2, 3]" or "[0; 64]", the opening bracket is already consumed
+    fn parse_array(&mut self) -> Result<Expression, String> {
+        if self.is_next("]") {
+            return Err("An array cannot be empty".to_string());
+        }
+
+        let first = self.parse_expression(0)?;
+        if self.is_next(";") {
+            self.next();
+            let count = match self.next() {
+                Some(Lexeme::Integer(count)) if count > 0 => count as usize,
+                _ => return Err(
+                    "The number of elements is a positive integer literal".to_string()
+                ),
+            };
+            self.expect("]")?;
+            return Ok(Expression::Repeat(Box::new(first), count));
+        }
+
+        let mut elements = vec![first];
+        while self.is_next(",") {
+            self.next();
+            elements.push(self.parse_expression(0)?);
+        }
+        self.expect("]")?;
+        Ok(Expression::Array(elements))
+    }
+
+    fn parse_operand(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Lexeme::Integer(value)) => Ok(Expression::Literal(Literal::Integer(value))),
+            Some(Lexeme::Float(value)) => Ok(Expression::Literal(Literal::Float(value))),
+            Some(Lexeme::Str(value)) => Ok(Expression::Literal(Literal::Str(value))),
+            Some(Lexeme::Word(word)) => match word.as_str() {
+                "true" => Ok(Expression::Literal(Literal::Bool(true))),
+                "false" => Ok(Expression::Literal(Literal::Bool(false))),
+                "not" => Ok(Expression::Unary(
+                    UnaryOperator::Not,
+                    Box::new(self.parse_expression(NOT_PRECEDENCE)?)
+                )),
+                "len" if self.is_next("(") => {
+                    self.next();
+                    let operand = self.parse_expression(0)?;
+                    self.expect(")")?;
+                    Ok(Expression::Len(Box::new(operand)))
+                }
+                _ if BinaryOperator::from_symbol(&word).is_some() => {
+                    Err(format!("An operand was expected before '{}'", word))
+                }
+                _ if self.is_next("{") => {
+                    self.next();
+                    self.parse_struct(word)
+                }
+                _ => Ok(Expression::Identifier(word)),
+            }
+            Some(Lexeme::Symbol(ref symbol)) if symbol == "(" => {
+                let expression = self.parse_expression(0)?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            Some(Lexeme::Symbol(ref symbol)) if symbol == "[" => self.parse_array(),
+            // A negative number is a literal, so "-128" can be a "i8"
+            Some(Lexeme::Symbol(ref symbol)) if symbol == "-" => match self.peek() {
+                Some(Lexeme::Integer(value)) => {
+                    let value = -*value;
+                    self.next();
+                    Ok(Expression::Literal(Literal::Integer(value)))
+                }
+                Some(Lexeme::Float(value)) => {
+                    let value = -*value;
+                    self.next();
+                    Ok(Expression::Literal(Literal::Float(value)))
+                }
+                _ => Ok(Expression::Unary(
+                    UnaryOperator::Negate,
+                    Box::new(self.parse_prefix()?)
+                )),
+            }
+            Some(Lexeme::Symbol(ref symbol)) if symbol == "~" => Ok(Expression::Unary(
+                UnaryOperator::BitNot,
+                Box::new(self.parse_prefix()?)
+            )),
+            Some(Lexeme::Symbol(ref symbol)) if symbol == "&" => Ok(Expression::Unary(
+                UnaryOperator::AddressOf,
+                Box::new(self.parse_prefix()?)
+            )),
+            Some(Lexeme::Symbol(ref symbol)) if symbol == "*" => Ok(Expression::Unary(
+                UnaryOperator::Dereference,
+                Box::new(self.parse_prefix()?)
+            )),
+            Some(lexeme) => Err(format!("Unexpected '{}' in the expression", lexeme)),
+            None => Err("An operand was expected at the end of the expression".to_string()),
+        }
+    }
+}
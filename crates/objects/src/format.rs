@@ -0,0 +1,152 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::expression::{Context, Expression, Literal};
+use crate::type_::Type;
+
+/// Specifier written after ':' in a placeholder to write an integer in
+/// hexadecimal : "{x:x}"
+pub const HEX: &str = "x";
+
+/// How an integer is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+    /// Signed or not according to the type of the value
+    Decimal,
+    /// Prefixed by "0x", only the bits of the type are written
+    Hexadecimal,
+}
+
+/// Part of a printed message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Piece {
+    /// Written as it is, the escape sequences like "\n" are kept
+    Text(String),
+    /// Value of an expression : "{x}" or "{x:x}"
+    Value(Expression, Radix),
+}
+
+/// Message written by "print" : a value, or a string literal where the values
+/// of the expressions between braces are inserted, like
+/// "x = {x}, y = {y:x}\n" \
+/// "{{" and "}}" are written as braces
+#[derive(Debug, Clone, PartialEq)]
+pub struct Format {
+    pub pieces: Vec<Piece>,
+}
+
+impl Format {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match Expression::parse(text)? {
+            Expression::Literal(Literal::Str(message)) => Self::parse_message(&message),
+            value => Ok(Self {
+                pieces: vec![Piece::Value(value, Radix::Decimal)],
+            }),
+        }
+    }
+
+    fn parse_message(message: &str) -> Result<Self, String> {
+        let mut pieces = vec![];
+        let mut text = String::new();
+
+        let mut chars = message.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(x) => placeholder.push(x),
+                            None => return Err(
+                                "A '{' is not closed by '}', write '{{' for a brace".to_string()
+                            ),
+                        }
+                    }
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(text.clone()));
+                        text.clear();
+                    }
+                    pieces.push(Self::parse_placeholder(&placeholder)?);
+                }
+                '}' => return Err(
+                    "A '}' is not opened by '{', write '}}' for a brace".to_string()
+                ),
+                _ => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Self {
+            pieces,
+        })
+    }
+
+    /// "<expression>" or "<expression>:<specifier>", without the braces
+    fn parse_placeholder(placeholder: &str) -> Result<Piece, String> {
+        let (expression, radix) = match placeholder.rsplit_once(':') {
+            Some((expression, HEX)) => (expression, Radix::Hexadecimal),
+            Some((_, specifier)) => return Err(format!(
+                "Unknown specifier '{}', only '{}' writes in hexadecimal",
+                specifier,
+                HEX
+            )),
+            None => (placeholder, Radix::Decimal),
+        };
+
+        if expression.trim().is_empty() {
+            return Err("A value is expected between '{' and '}'".to_string());
+        }
+        Ok(Piece::Value(Expression::parse(expression)?, radix))
+    }
+
+    /// Check that each value can be written : an integer, an enum as its
+    /// integer, a boolean, a pointer in hexadecimal or a string variable
+    pub fn check(&self, context: &dyn Context) -> Result<(), String> {
+        for piece in self.pieces.iter() {
+            let (value, radix) = match piece {
+                Piece::Value(value, radix) => (value, radix),
+                Piece::Text(_) => continue,
+            };
+
+            let type_ = value.type_(context)?;
+            let is_printable = match type_ {
+                Type::Bool => *radix == Radix::Decimal,
+                // Written from the address of its variable, a static or a
+                // local variable holds the whole text and a parameter
+                // cannot be a string
+                Type::Str => {
+                    *radix == Radix::Decimal && matches!(value, Expression::Identifier(_))
+                }
+                _ => type_.is_integer() || type_.is_enum() || type_.is_pointer(),
+            };
+
+            if !is_printable {
+                return Err(match *radix {
+                    Radix::Hexadecimal => format!(
+                        "'{}' gives '{}', only an integer or a pointer is written in hexadecimal",
+                        value,
+                        type_
+                    ),
+                    Radix::Decimal => format!(
+                        "'{}' gives '{}' which cannot be printed",
+                        value,
+                        type_
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,10 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use crate::expression::Context;
 use crate::params::Params;
+use crate::type_::Type;
+use crate::variable::{Definition, Variable};
 
 /// Structure used to create a function \
 /// Because it's not an interpreter, this is stored following the function, this
@@ -12,11 +15,12 @@ use crate::params::Params;
 pub struct Function {
     id: String,
     params: Params,
-    return_type: String,
+    /// `None` when the function returns nothing
+    return_type: Option<Type>,
 }
 
 impl Function {
-    pub fn new(id: String, params: Params, return_type: String) -> Self {
+    pub fn new(id: String, params: Params, return_type: Option<Type>) -> Self {
         Self {
             id,
             params,
@@ -30,7 +34,116 @@ impl Function {
     pub fn params(&self) -> &Params {
         &self.params
     }
-    pub fn return_type(&self) -> &String {
+    pub fn return_type(&self) -> &Option<Type> {
         &self.return_type
     }
+
+    pub fn set_return_type(&mut self, return_type: Option<Type>) {
+        self.return_type = return_type;
+    }
+}
+
+/// Function as declared after "func" : "<id>(<param>: <type>, ...): <type>"
+/// \
+/// The parentheses can be omitted when there is no parameter, and the return
+/// type when nothing is returned
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub id: String,
+    /// Written like variables definitions, with a type and without value
+    pub params: Vec<Definition>,
+    pub return_type: Option<String>,
+}
+
+impl Signature {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (id, params, rest) = match text.find('(') {
+            Some(open) => match text[open..].find(')') {
+                Some(close) => (
+                    &text[..open],
+                    &text[open + 1..open + close],
+                    &text[open + close + 1..],
+                ),
+                None => return Err("The parameters are not closed by ')'".to_string()),
+            }
+            None => match text.find(':') {
+                Some(colon) => (&text[..colon], "", &text[colon..]),
+                None => (text, "", ""),
+            }
+        };
+
+        let return_type = match rest.trim() {
+            "" => None,
+            rest => match rest.strip_prefix(':') {
+                Some(type_) if !type_.trim().is_empty() => Some(type_.trim().to_string()),
+                Some(_) => return Err("No return type is written after ':'".to_string()),
+                None => return Err(format!("Unexpected '{}' after the parameters", rest)),
+            }
+        };
+
+        let mut definitions: Vec<Definition> = vec![];
+        for param in split_params(params) {
+            let definition = Definition::parse(param);
+
+            if definition.type_.is_none() {
+                return Err(format!("The parameter '{}' has no type", definition.id));
+            }
+            if definition.value.is_some() {
+                return Err(format!("The parameter '{}' cannot have a value", definition.id));
+            }
+            if definitions.iter().any(| x | x.id == definition.id) {
+                return Err(format!("The parameter '{}' is declared many times", definition.id));
+            }
+            definitions.push(definition);
+        }
+
+        Ok(Self {
+            id: id.trim().to_string(),
+            params: definitions,
+            return_type,
+        })
+    }
+
+    /// Function named `id` declared by the signature, the types of its
+    /// parameters and its return type are resolved in `context`
+    pub fn function(&self, id: String, context: &dyn Context) -> Function {
+        let params = self.params.iter()
+            .map(| x | Variable::new(
+                x.id.clone(),
+                Type::from_string(x.type_.clone().unwrap_or_default()).resolve(context),
+                String::new(),
+                0
+            ))
+            .collect();
+
+        let return_type = self.return_type.as_ref()
+            .map(| x | Type::from_string(x.clone()).resolve(context));
+
+        Function::new(id, params, return_type)
+    }
+}
+
+/// Split the parameters at their commas, the commas in the brackets of an
+/// array type are not separators
+fn split_params(params: &str) -> Vec<&str> {
+    if params.trim().is_empty() {
+        return vec![];
+    }
+
+    let mut split = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(params[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(params[start..].trim());
+    split
 }
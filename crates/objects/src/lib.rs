@@ -2,7 +2,13 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+pub mod constant;
+pub mod enumeration;
+pub mod expression;
+pub mod format;
 pub mod function;
 pub mod params;
+pub mod range;
+pub mod structure;
 pub mod type_;
 pub mod variable;
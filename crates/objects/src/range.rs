@@ -0,0 +1,100 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::expression::{fits, Context, Expression};
+use crate::type_::Type;
+
+/// Keyword written before the step of a range
+pub const STEP: &str = "step";
+
+/// Values taken by the counter of a loop "for", written after the keyword :
+/// "<id> in <start>..<end>", or "<start>..=<end>" to include the end, followed
+/// by "step <step>" to count with another step than one \
+/// The range is descending when the step is negative
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub counter: String,
+    pub start: Expression,
+    pub end: Expression,
+    pub is_inclusive: bool,
+    pub step: Option<Expression>,
+}
+
+impl Range {
+    /// Example: "i in 0..n step 2" counts "i" from 0 to "n", excluded, with
+    /// a step of 2
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (counter, range) = match text.split_once(" in ") {
+            Some((counter, range)) => (counter.trim(), range.trim()),
+            None => return Err("Expected '<id> in <start>..<end>'".to_string()),
+        };
+        if counter.is_empty() {
+            return Err("The counter is not named before 'in'".to_string());
+        }
+
+        let (range, step) = match range.split_once(&format!(" {} ", STEP)) {
+            Some((range, step)) => (range.trim(), Some(Expression::parse(step.trim())?)),
+            None => (range, None),
+        };
+
+        let (start, end, is_inclusive) = match range.split_once("..") {
+            Some((start, end)) => match end.strip_prefix('=') {
+                Some(end) => (start, end, true),
+                None => (start, end, false),
+            }
+            None => return Err(format!(
+                "Expected a range like '<start>..<end>' but found '{}'",
+                range
+            )),
+        };
+
+        Ok(Self {
+            counter: counter.to_string(),
+            start: Expression::parse(start.trim())?,
+            end: Expression::parse(end.trim())?,
+            is_inclusive,
+            step,
+        })
+    }
+
+    /// Type of the counter, the integer type of the start and the end
+    pub fn type_(&self, context: &dyn Context) -> Result<Type, String> {
+        let type_ = Expression::operands_type(&self.start, &self.end, context)?;
+        if !type_.is_integer() {
+            return Err(format!(
+                "A range counts integers, '{}' and '{}' give '{}'",
+                self.start,
+                self.end,
+                type_
+            ));
+        }
+        Ok(type_)
+    }
+
+    /// Value added to the counter after each iteration, one when no step is
+    /// written \
+    /// It's known at compile time, not zero, and its size fits the type of
+    /// the counter
+    pub fn step_value(&self, type_: &Type, context: &dyn Context) -> Result<i128, String> {
+        let step = match self.step {
+            Some(ref step) => step,
+            None => return Ok(1),
+        };
+
+        let value = step.constant_value(context)
+            .map_err(| _ | format!("The step '{}' is not known at compile time", step))?;
+
+        if value == 0 {
+            return Err("The step cannot be zero, the loop would never end".to_string());
+        }
+        if !fits(value.abs(), type_) {
+            return Err(format!(
+                "The step '{}' is too big for a counter of type '{}'",
+                step,
+                type_
+            ));
+        }
+        Ok(value)
+    }
+}
@@ -0,0 +1,109 @@
+// This file is part of "juc"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::type_::Type;
+
+/// Field of a structure, placed at `offset` bytes from the start of the
+/// structure
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Field {
+    pub id: String,
+    pub type_: Type,
+    pub offset: usize,
+}
+
+/// Class of an eightbyte of an aggregate passed by value, following the
+/// System V ABI
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Class {
+    /// Passed in a general purpose register
+    Integer,
+    /// Passed in a "xmm" register
+    Sse,
+}
+
+/// Structure declared in the source code, with its memory layout : the fields
+/// are placed in the order of their declaration, each one at an address
+/// multiple of its alignment
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Struct {
+    /// Scoped name, like "geometry.Point"
+    pub id: String,
+    /// Name as written in the source code, like "Point"
+    pub name: String,
+    pub fields: Vec<Field>,
+    /// Multiple of the alignment, the padding at the end keeps the elements
+    /// of an array aligned
+    pub size: usize,
+    pub alignment: usize,
+}
+
+impl Struct {
+    /// Compute the layout of the fields, their types have to be known
+    pub fn new(id: String, name: String, fields: Vec<(String, Type)>) -> Self {
+        let mut size = 0;
+        let mut alignment = 1;
+
+        let fields = fields.into_iter()
+            .map(| (id, type_) | {
+                let offset = align(size, type_.alignment());
+                size = offset + type_.to_usize();
+                alignment = alignment.max(type_.alignment());
+
+                Field {
+                    id,
+                    type_,
+                    offset,
+                }
+            })
+            .collect();
+
+        Self {
+            id,
+            name,
+            fields,
+            size: align(size, alignment),
+            alignment,
+        }
+    }
+
+    pub fn field(&self, id: &str) -> Option<&Field> {
+        self.fields.iter().find(| x | x.id == id)
+    }
+
+    /// Classes of the eightbytes of the structure when it's passed by value
+    /// or returned, `None` when it's passed in memory because it's bigger
+    /// than two eightbytes \
+    /// An eightbyte is passed in a "xmm" register when it only contains
+    /// floats, otherwise in a general purpose register
+    pub fn classes(&self) -> Option<Vec<Class>> {
+        if self.size > 16 {
+            return None;
+        }
+
+        let scalars = Type::Struct(Box::new(self.clone())).scalars();
+        Some(
+            (0..(self.size + 7) / 8)
+                .map(| i | {
+                    let only_floats = scalars.iter()
+                        .filter(| (offset, _) | offset / 8 == i)
+                        .all(| (_, type_) | type_.is_float());
+
+                    match only_floats {
+                        true => Class::Sse,
+                        false => Class::Integer,
+                    }
+                })
+                .collect()
+        )
+    }
+}
+
+/// First multiple of `alignment` from `offset`
+pub fn align(offset: usize, alignment: usize) -> usize {
+    match offset % alignment {
+        0 => offset,
+        rest => offset + alignment - rest,
+    }
+}
@@ -2,50 +2,305 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use std::fmt;
+
 use x64asm::ddirective;
 use x64asm::ddirective::DefineDirective::*;
 use x64asm::operand::Operand;
 
+use crate::enumeration::Enum;
+use crate::expression::{Context, Expression};
+use crate::structure::Struct;
+
 #[allow(unused)]
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Type {
-    Byte, // 1 Byte 
-    Integer, // 4 Bytes like an `i32`
-    BigInteger, // 8 bytes like an `i64`
+    I8, // 1 Byte, signed
+    I16, // 2 Bytes, signed
+    I32, // 4 Bytes, signed, also named "int"
+    I64, // 8 Bytes, signed, also named "bigint"
+    U8, // 1 Byte, unsigned, also named "byte"
+    U16, // 2 Bytes, unsigned
+    U32, // 4 Bytes, unsigned
+    U64, // 8 Bytes, unsigned
+    Bool, // 1 Byte, "0" or "1"
+    F32, // 4 Bytes, single precision float
+    F64, // 8 Bytes, double precision float
 
     Str, // len(str) * sizeof(Byte)
+    /// Type of the elements and number of elements : "[T; N]"
+    Array(Box<Type>, usize),
+    /// Address of a value of the pointed type : "*T", 8 Bytes
+    Pointer(Box<Type>),
+    /// Structure declared in the source code, with its layout
+    Struct(Box<Struct>),
+    /// Enum declared in the source code, stored as its integer type
+    Enum(Box<Enum>),
+    /// Name of a type that is not known yet, SEE `Type::resolve()`
     NotNative(String),
 }
 
 impl Type {
     pub fn from_string(type_as_string: String) -> Self {
         match type_as_string.as_str() {
-            "byte" => Type::Byte,
-            "int" => Type::Integer,
-            "bigint" => Type::BigInteger,
+            "i8" => Type::I8,
+            "i16" => Type::I16,
+            "i32" | "int" => Type::I32,
+            "i64" | "bigint" => Type::I64,
+            "u8" | "byte" => Type::U8,
+            "u16" => Type::U16,
+            "u32" => Type::U32,
+            "u64" => Type::U64,
+            "bool" => Type::Bool,
+            "f32" => Type::F32,
+            "f64" => Type::F64,
             "str" => Type::Str,
-            _ => Type::NotNative(type_as_string),
+            _ if type_as_string.starts_with('*') => Type::Pointer(Box::new(
+                Type::from_string(type_as_string[1..].trim().to_string())
+            )),
+            _ => match Self::array_from_string(&type_as_string) {
+                Some(array) => array,
+                None => Type::NotNative(type_as_string),
+            }
+        }
+    }
+
+    /// Parse "[T; N]", with N an integer literal
+    fn array_from_string(type_as_string: &str) -> Option<Self> {
+        let inner = type_as_string.trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?;
+
+        let (element, count) = inner.rsplit_once(';')?;
+        let count = count.trim().parse::<usize>().ok()?;
+
+        Some(Type::Array(
+            Box::new(Type::from_string(element.trim().to_string())),
+            count
+        ))
+    }
+
+    /// Replace the names of the structures and enums by their declarations and
+    /// the constants giving the number of elements of the arrays by their
+    /// values, the unknown names stay as they are \
+    /// The pointed type of a pointer is only resolved when it's dereferenced,
+    /// SEE `Type::by_name()`
+    pub fn resolve(&self, context: &dyn Context) -> Self {
+        match *self {
+            Type::Array(ref element, count) => {
+                Type::Array(Box::new(element.resolve(context)), count)
+            }
+            Type::Pointer(ref pointee) => Type::Pointer(Box::new(pointee.by_name(context))),
+            Type::NotNative(ref type_as_string) => match context.struct_(type_as_string) {
+                Some(struct_) => Type::Struct(Box::new(struct_)),
+                None => match context.enum_(type_as_string) {
+                    Some(enum_) => Type::Enum(Box::new(enum_)),
+                    None => match Self::sized_array(type_as_string, context) {
+                        Some(array) => array,
+                        None => self.clone(),
+                    }
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Parse "[T; N]", with N a constant expression like "SIZE * 2"
+    fn sized_array(type_as_string: &str, context: &dyn Context) -> Option<Self> {
+        let inner = type_as_string.trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?;
+
+        let (element, count) = inner.rsplit_once(';')?;
+        let count = Expression::parse(count.trim()).ok()?
+            .constant_value(context).ok()?;
+
+        Some(Type::Array(
+            Box::new(Type::from_string(element.trim().to_string()).resolve(context)),
+            usize::try_from(count).ok()?
+        ))
+    }
+
+    /// Same type where the structures are given by their scoped names, like
+    /// the pointed type of a pointer : a structure can contain a pointer to
+    /// itself and two pointers to the same structure have the same type
+    pub fn by_name(&self, context: &dyn Context) -> Self {
+        match *self {
+            Type::Array(ref element, count) => {
+                Type::Array(Box::new(element.by_name(context)), count)
+            }
+            Type::Pointer(ref pointee) => Type::Pointer(Box::new(pointee.by_name(context))),
+            Type::Struct(ref struct_) => Type::NotNative(struct_.id.clone()),
+            Type::Enum(ref enum_) => Type::NotNative(enum_.id.clone()),
+            Type::NotNative(ref type_as_string) => match context.struct_(type_as_string) {
+                Some(struct_) => Type::NotNative(struct_.id),
+                None => match context.enum_(type_as_string) {
+                    Some(enum_) => Type::NotNative(enum_.id),
+                    None => self.clone(),
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Name of the first type that was not resolved, if there is one \
+    /// The pointed types are not resolved, their size is not needed
+    pub fn unknown(&self) -> Option<&String> {
+        match *self {
+            Type::Array(ref element, _) => element.unknown(),
+            Type::NotNative(ref type_as_string) => Some(type_as_string),
+            _ => None,
+        }
+    }
+
+    /// Type of the elements of the arrays and of the values pointed by the
+    /// pointers, like "int" for "[*int; 4]"
+    pub fn innermost(&self) -> &Type {
+        match *self {
+            Type::Array(ref x, _) | Type::Pointer(ref x) => x.innermost(),
+            _ => self,
         }
     }
 
     pub fn to_asm_operand(&self) -> Operand {
+        // The elements of an array are defined one after the other, the
+        // fields of a structure are defined one by one
+        match *self {
+            Type::Array(ref element, _) => return element.to_asm_operand(),
+            Type::Struct(_) => return ddirective!(Db),
+            _ => {}
+        }
+
         // The `ddirective!()` macro create an operand object
-        ddirective!(match *self {
-            Type::Byte => Db,
-            Type::Integer => Dd,
-            Type::BigInteger => Dq,
-            Type::Str => Db,
-            Type::NotNative(ref _type_as_string)=> todo!(),
+        ddirective!(match self.to_usize() {
+            _ if *self == Type::Str => Db,
+            1 => Db,
+            2 => Dw,
+            4 => Dd,
+            _ => Dq,
         })
     }
 
     pub fn to_usize(&self) -> usize {
         match *self {
-            Type::Byte => 1,
-            Type::Integer => 4,
-            Type::BigInteger => 8,
+            Type::I8 | Type::U8 | Type::Bool => 1,
+            Type::I16 | Type::U16 => 2,
+            Type::I32 | Type::U32 | Type::F32 => 4,
+            Type::I64 | Type::U64 | Type::F64 | Type::Pointer(_) => 8,
             Type::Str => 1,
-            Type::NotNative(ref _type_as_string) => todo!()
+            Type::Array(ref element, count) => element.to_usize() * count,
+            Type::Struct(ref struct_) => struct_.size,
+            Type::Enum(ref enum_) => enum_.type_.to_usize(),
+            // Never happens, the unknown types are reported by "types"
+            Type::NotNative(_) => panic!(),
+        }
+    }
+
+    /// A value of this type is placed at an address multiple of its alignment
+    pub fn alignment(&self) -> usize {
+        match *self {
+            Type::Array(ref element, _) => element.alignment(),
+            Type::Struct(ref struct_) => struct_.alignment,
+            _ => self.to_usize(),
+        }
+    }
+
+    /// Values that are not kept in a register but only in memory
+    pub fn is_aggregate(&self) -> bool {
+        matches!(*self, Type::Array(_, _) | Type::Struct(_))
+    }
+
+    /// Values that are not arrays or structures making a value of this
+    /// type, with their offsets, in the order of the memory
+    pub fn scalars(&self) -> Vec<(usize, Type)> {
+        match *self {
+            Type::Array(ref element, count) => (0..count)
+                .flat_map(| i | element.scalars()
+                    .into_iter()
+                    .map(move | (offset, x) | (i * element.to_usize() + offset, x))
+                )
+                .collect(),
+            Type::Struct(ref struct_) => struct_.fields.iter()
+                .flat_map(| field | field.type_.scalars()
+                    .into_iter()
+                    .map(move | (offset, x) | (field.offset + offset, x))
+                )
+                .collect(),
+            _ => vec![(0, self.clone())],
+        }
+    }
+
+    /// Size keyword of an assembly operand for a value of this type, like in
+    /// "mov dword [rbp-4], 5"
+    pub fn operand_size(&self) -> &'static str {
+        match self.to_usize() {
+            1 => "byte",
+            2 => "word",
+            4 => "dword",
+            _ => "qword",
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.integer_range().is_some()
+    }
+
+    pub fn is_signed(&self) -> bool {
+        match *self {
+            Type::Enum(ref enum_) => enum_.type_.is_signed(),
+            _ => matches!(*self, Type::I8 | Type::I16 | Type::I32 | Type::I64),
+        }
+    }
+
+    /// Minimum and maximum values of an integer type
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        Some(match *self {
+            Type::I8 => (i8::MIN as i128, i8::MAX as i128),
+            Type::I16 => (i16::MIN as i128, i16::MAX as i128),
+            Type::I32 => (i32::MIN as i128, i32::MAX as i128),
+            Type::I64 => (i64::MIN as i128, i64::MAX as i128),
+            Type::U8 => (0, u8::MAX as i128),
+            Type::U16 => (0, u16::MAX as i128),
+            Type::U32 => (0, u32::MAX as i128),
+            Type::U64 => (0, u64::MAX as i128),
+            _ => return None,
+        })
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        matches!(*self, Type::Pointer(_))
+    }
+
+    pub fn is_enum(&self) -> bool {
+        matches!(*self, Type::Enum(_))
+    }
+
+    /// Kept in a "xmm" register instead of a general purpose register
+    pub fn is_float(&self) -> bool {
+        matches!(*self, Type::F32 | Type::F64)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Type::I8 => write!(f, "i8"),
+            Type::I16 => write!(f, "i16"),
+            Type::I32 => write!(f, "i32"),
+            Type::I64 => write!(f, "i64"),
+            Type::U8 => write!(f, "u8"),
+            Type::U16 => write!(f, "u16"),
+            Type::U32 => write!(f, "u32"),
+            Type::U64 => write!(f, "u64"),
+            Type::Bool => write!(f, "bool"),
+            Type::F32 => write!(f, "f32"),
+            Type::F64 => write!(f, "f64"),
+            Type::Str => write!(f, "str"),
+            Type::Array(ref element, count) => write!(f, "[{}; {}]", element, count),
+            Type::Pointer(ref pointee) => write!(f, "*{}", pointee),
+            Type::Struct(ref struct_) => write!(f, "{}", struct_.name),
+            Type::Enum(ref enum_) => write!(f, "{}", enum_.name),
+            Type::NotNative(ref type_as_string) => write!(f, "{}", type_as_string),
         }
     }
 }
@@ -2,13 +2,14 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use crate::expression::{split_assignment, Context, Expression};
 use crate::type_::Type;
 
 /// Structure used to create a variable \
 /// Because it's not an interpreter, this is stored following the variable, this
 /// structure should only be used by defining a variable in ASM
 #[derive(PartialEq, Eq, Hash)] // needed for `Dict` objects
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Variable {
     id: String,
     type_: Type,
@@ -58,3 +59,46 @@ impl Variable {
         &self.stack_pos
     }
 }
+
+/// Definition of a variable as written after "let" or "static" : 
+/// "<id>: <type> = <value>", where the type and the value are optional
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub id: String,
+    pub type_: Option<String>,
+    /// Not parsed, it could be an expression or a string
+    pub value: Option<String>,
+}
+
+impl Definition {
+    pub fn parse(text: &str) -> Self {
+        let (declaration, value) = match split_assignment(text) {
+            Some((declaration, value)) => (declaration, Some(value.to_string())),
+            None => (text.trim(), None),
+        };
+
+        let (id, type_) = match declaration.split_once(':') {
+            Some((id, type_)) => (id.trim(), Some(type_.trim().to_string())),
+            None => (declaration, None),
+        };
+
+        Self {
+            id: id.to_string(),
+            type_,
+            value,
+        }
+    }
+
+    /// The written type or, when there is none, the type of the value \
+    /// Example: "c = 1" gives "i32"
+    pub fn infer_type(&self, context: &dyn Context) -> Result<Type, String> {
+        if let Some(ref type_) = self.type_ {
+            return Ok(Type::from_string(type_.clone()).resolve(context));
+        }
+
+        match self.value {
+            Some(ref value) => Expression::parse(value)?.type_(context),
+            None => Err(format!("'{}' has no type and no value giving one", self.id)),
+        }
+    }
+}
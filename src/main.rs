@@ -117,7 +117,13 @@ fn help() {
         + "\t-p <platform name> : Compile for this platform\n"
         + "\t\t(Android, IOS, Linux, MacOS, Windows)\n"
         + "\t-o <path> : Path for the output file\n"
-        + "\t-d <path> : Replace the current directory context location\n";
+        + "\t-d <path> : Replace the current directory context location\n"
+        + "\n"
+        + "Available long options:\n"
+        + "\t--only-check=<names> : Only run these checkers (separated by commas)\n"
+        + "\t--skip-check=<names> : Do not run these checkers (separated by commas)\n"
+        + "\t--no-bounds-checks : Do not check the array indexes at runtime\n"
+        + "\t--emit : Write the generated assembly of each source file\n";
 
     print!("\x1b[1m{}\x1b[0m", to_write);
     process::exit(0);
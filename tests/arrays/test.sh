@@ -0,0 +1,15 @@
+#!/bin/bash
+
+source tests/test.sh
+
+# The last index is out of bounds, an error message is written
+print_test_name
+$BIN -d tests/arrays/ test.ju -o test_arrays
+./tests/arrays/test_arrays
+print_test_end $?
+
+# Same without checking the bounds
+print_test_name
+$BIN -d tests/arrays/ test.ju -o test_arrays --no-bounds-checks
+./tests/arrays/test_arrays
+print_test_end $?
@@ -0,0 +1,9 @@
+#!/bin/bash
+
+source tests/test.sh
+
+# unknown instruction, invalid register, wrong operands count and unknown
+# symbol
+print_test_name
+$BIN -d tests/asm/ test.ju -o test_asm
+print_test_end $?
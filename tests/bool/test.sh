@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/bool/ test.ju -o test_bool
+./tests/bool/test_bool
+print_test_end $?
@@ -0,0 +1,21 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/checkers/ test.ju -o test_checkers --only-check=syntax,entry
+print_test_end $?
+
+print_test_name
+$BIN -d tests/checkers/ test.ju -o test_checkers --skip-check=flow
+print_test_end $?
+
+# unknown checker name, only a warning
+print_test_name
+$BIN -d tests/checkers/ test.ju -o test_checkers --skip-check=nothing
+print_test_end $?
+
+# required checker, only a warning and it's run anyway
+print_test_name
+$BIN -d tests/checkers/ test.ju -o test_checkers --skip-check=types
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/conditions/ test.ju -o test_conditions
+./tests/conditions/test_conditions
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/consts/ test.ju -o test_consts
+./tests/consts/test_consts
+print_test_end $?
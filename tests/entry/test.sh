@@ -0,0 +1,18 @@
+#!/bin/bash
+
+source tests/test.sh
+
+# no entry point in a binary
+print_test_name
+$BIN -d tests/entry/ lib.ju -o test_entry
+print_test_end $?
+
+# multiple entry points
+print_test_name
+$BIN -d tests/entry/ a.ju b.ju -o test_entry
+print_test_end $?
+
+# entry point in a library, only a warning
+print_test_name
+$BIN -d tests/entry/ a.ju -l -o test_entry.so
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/enums/ test.ju -o test_enums
+./tests/enums/test_enums
+print_test_end $?
@@ -0,0 +1,14 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/expressions/ test.ju -o test_expressions
+./tests/expressions/test_expressions
+print_test_end $?
+
+# The division by zero writes an error message
+print_test_name
+$BIN -d tests/expressions/ zero.ju -o test_expressions
+./tests/expressions/test_expressions
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/floats/ test.ju -o test_floats
+./tests/floats/test_floats
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/flow/ test.ju -o test_flow
+./tests/flow/test_flow
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/frames/ test.ju -o test_frames
+./tests/frames/test_frames
+print_test_end $?
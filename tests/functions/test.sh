@@ -0,0 +1,13 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/functions/ main.ju lib.ju -o test_functions
+./tests/functions/test_functions
+print_test_end $?
+
+# "add" takes two arguments
+print_test_name
+$BIN -d tests/functions/ invalid.ju -o test_functions
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+# "rax", "if" and "9a" are not valid identifiers
+print_test_name
+$BIN -d tests/identifiers/ test.ju -o test_identifiers
+print_test_end $?
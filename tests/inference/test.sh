@@ -0,0 +1,9 @@
+#!/bin/bash
+
+source tests/test.sh
+
+# The inferred types are written as comments of the generated assembly
+print_test_name
+$BIN -d tests/inference/ test.ju -o test_inference --emit
+./tests/inference/test_inference
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/integers/ test.ju -o test_integers
+./tests/integers/test_integers
+print_test_end $?
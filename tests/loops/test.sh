@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/loops/ test.ju -o test_loops
+./tests/loops/test_loops
+print_test_end $?
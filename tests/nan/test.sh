@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/nan/ test.ju -o test_nan
+./tests/nan/test_nan
+print_test_end $?
@@ -0,0 +1,12 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/params/ test.ju -o test_params
+./tests/params/test_params
+RESULT=$?
+print_test_end $RESULT
+
+# Each function gives 1 when it reads a wrong parameter
+exit $RESULT
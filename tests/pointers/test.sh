@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/pointers/ test.ju -o test_pointers
+./tests/pointers/test_pointers
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/prints/ test.ju -o test_prints
+./tests/prints/test_prints
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/ranges/ test.ju -o test_ranges
+./tests/ranges/test_ranges
+print_test_end $?
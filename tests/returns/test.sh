@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/returns/ test.ju -o test_returns
+./tests/returns/test_returns
+print_test_end $?
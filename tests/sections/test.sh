@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/sections/ test.ju -o test_sections
+./tests/sections/test_sections
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/strings/ test.ju -o test_strings
+./tests/strings/test_strings
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/structs/ test.ju -o test_structs
+./tests/structs/test_structs
+print_test_end $?
@@ -0,0 +1,13 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/symbols/ main.ju lib.ju -o test_symbols
+./tests/symbols/test_symbols
+print_test_end $?
+
+# "exit" is ambiguous
+print_test_name
+$BIN -d tests/symbols/ main.ju lib.ju other.ju -o test_symbols
+print_test_end $?
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+source tests/test.sh
+
+print_test_name
+$BIN -d tests/wrapping/ test.ju -o test_wrapping
+./tests/wrapping/test_wrapping
+print_test_end $?